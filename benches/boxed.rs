@@ -0,0 +1,59 @@
+// Allocation churn of boxed::Box against std::boxed::Box
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rust_samples::boxed::boxed::Box;
+use std::hint::black_box;
+
+const COUNT: usize = 1024;
+
+fn churn(c: &mut Criterion) {
+    let mut group = c.benchmark_group("boxed/churn");
+    group.bench_function("std", |b| {
+        b.iter(|| {
+            for value in 0..COUNT {
+                black_box(std::boxed::Box::new(black_box(value)));
+            }
+        })
+    });
+    group.bench_function("crate", |b| {
+        b.iter(|| {
+            for value in 0..COUNT {
+                black_box(Box::new(black_box(value)));
+            }
+        })
+    });
+    group.finish();
+}
+
+// Many boxes alive at once, then freed together
+fn batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("boxed/batch");
+    group.bench_function(BenchmarkId::new("std", COUNT), |b| {
+        b.iter(|| {
+            (0..COUNT)
+                .map(|value| std::boxed::Box::new([value; 8]))
+                .collect::<std::vec::Vec<_>>()
+        })
+    });
+    group.bench_function(BenchmarkId::new("crate", COUNT), |b| {
+        b.iter(|| {
+            (0..COUNT)
+                .map(|value| Box::new([value; 8]))
+                .collect::<std::vec::Vec<_>>()
+        })
+    });
+    group.finish();
+}
+
+fn into_inner(c: &mut Criterion) {
+    let mut group = c.benchmark_group("boxed/into_inner");
+    group.bench_function("std", |b| {
+        b.iter(|| *std::boxed::Box::new(black_box(String::from("Hello"))))
+    });
+    group.bench_function("crate", |b| {
+        b.iter(|| Box::into_inner(Box::new(black_box(String::from("Hello")))))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, churn, batch, into_inner);
+criterion_main!(benches);
@@ -0,0 +1,121 @@
+// Either combinators against the same chains on Result, Right plays the role of Ok
+use criterion::{criterion_group, criterion_main, Criterion};
+use rust_samples::either::either::{Either, IteratorExt};
+use std::hint::black_box;
+
+const COUNT: i64 = 1024;
+
+fn combinators(c: &mut Criterion) {
+    let mut group = c.benchmark_group("either/combinators");
+    group.bench_function("std", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            for value in 0..COUNT {
+                let result: Result<i64, i64> = if value % 3 == 0 {
+                    Err(value)
+                } else {
+                    Ok(value)
+                };
+                sum += black_box(result)
+                    .map(|value| value * 2)
+                    .and_then(|value| {
+                        if value % 5 == 0 {
+                            Err(value)
+                        } else {
+                            Ok(value)
+                        }
+                    })
+                    .map_err(|value| value + 1)
+                    .unwrap_or_else(|value| -value);
+            }
+            sum
+        })
+    });
+    group.bench_function("crate", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            for value in 0..COUNT {
+                let either: Either<i64, i64> = if value % 3 == 0 {
+                    Either::Left(value)
+                } else {
+                    Either::Right(value)
+                };
+                sum += black_box(either)
+                    .map(|value| value * 2)
+                    .and_then(|value| {
+                        if value % 5 == 0 {
+                            Either::Left(value)
+                        } else {
+                            Either::Right(value)
+                        }
+                    })
+                    .map_left(|value| value + 1)
+                    .unwrap_or_else(|value| -value);
+            }
+            sum
+        })
+    });
+    group.finish();
+}
+
+fn partition(c: &mut Criterion) {
+    let mut group = c.benchmark_group("either/partition");
+    let results: std::vec::Vec<Result<i64, i64>> = (0..COUNT)
+        .map(|value| {
+            if value % 3 == 0 {
+                Err(value)
+            } else {
+                Ok(value)
+            }
+        })
+        .collect();
+    let eithers: std::vec::Vec<Either<i64, i64>> =
+        results.iter().copied().map(Either::from).collect();
+    group.bench_function("std", |b| {
+        b.iter(|| {
+            let mut errs = std::vec::Vec::new();
+            let mut oks = std::vec::Vec::new();
+            for result in black_box(&results) {
+                match *result {
+                    Ok(value) => oks.push(value),
+                    Err(value) => errs.push(value),
+                }
+            }
+            (errs, oks)
+        })
+    });
+    group.bench_function("crate", |b| {
+        b.iter(|| {
+            let (lefts, rights): (std::vec::Vec<i64>, std::vec::Vec<i64>) =
+                black_box(&eithers).iter().copied().partition_either();
+            (lefts, rights)
+        })
+    });
+    group.finish();
+}
+
+fn collect(c: &mut Criterion) {
+    let mut group = c.benchmark_group("either/collect");
+    let results: std::vec::Vec<Result<i64, i64>> = (0..COUNT).map(Ok).collect();
+    let eithers: std::vec::Vec<Either<i64, i64>> = (0..COUNT).map(Either::Right).collect();
+    group.bench_function("std", |b| {
+        b.iter(|| {
+            black_box(&results)
+                .iter()
+                .copied()
+                .collect::<Result<std::vec::Vec<i64>, i64>>()
+        })
+    });
+    group.bench_function("crate", |b| {
+        b.iter(|| {
+            black_box(&eithers)
+                .iter()
+                .copied()
+                .collect::<Either<i64, std::vec::Vec<i64>>>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, combinators, partition, collect);
+criterion_main!(benches);
@@ -0,0 +1,93 @@
+// Reference counting of rc::Rc and rc::Weak against std::rc
+use criterion::{criterion_group, criterion_main, Criterion};
+use rust_samples::rc::rc::Rc;
+use std::hint::black_box;
+
+const COUNT: usize = 1024;
+
+fn new(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rc/new");
+    group.bench_function("std", |b| b.iter(|| std::rc::Rc::new(black_box(5))));
+    group.bench_function("crate", |b| b.iter(|| Rc::new(black_box(5))));
+    group.finish();
+}
+
+fn clone_drop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rc/clone_drop");
+    let expected = std::rc::Rc::new(5);
+    let actual = Rc::new(5);
+    group.bench_function("std", |b| {
+        b.iter(|| {
+            for _ in 0..COUNT {
+                drop(black_box(std::rc::Rc::clone(&expected)));
+            }
+        })
+    });
+    group.bench_function("crate", |b| {
+        b.iter(|| {
+            for _ in 0..COUNT {
+                drop(black_box(actual.clone()));
+            }
+        })
+    });
+    group.finish();
+}
+
+fn upgrade(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rc/upgrade");
+    let expected = std::rc::Rc::new(5);
+    let expected_weak = std::rc::Rc::downgrade(&expected);
+    let actual = Rc::new(5);
+    let actual_weak = Rc::downgrade(&actual);
+    group.bench_function("std", |b| {
+        b.iter(|| {
+            for _ in 0..COUNT {
+                black_box(expected_weak.upgrade());
+            }
+        })
+    });
+    group.bench_function("crate", |b| {
+        b.iter(|| {
+            for _ in 0..COUNT {
+                black_box(actual_weak.upgrade());
+            }
+        })
+    });
+    group.finish();
+}
+
+fn downgrade(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rc/downgrade");
+    let expected = std::rc::Rc::new(5);
+    let actual = Rc::new(5);
+    group.bench_function("std", |b| {
+        b.iter(|| {
+            for _ in 0..COUNT {
+                drop(black_box(std::rc::Rc::downgrade(&expected)));
+            }
+        })
+    });
+    group.bench_function("crate", |b| {
+        b.iter(|| {
+            for _ in 0..COUNT {
+                drop(black_box(Rc::downgrade(&actual)));
+            }
+        })
+    });
+    group.finish();
+}
+
+fn slice(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rc/from_slice");
+    let values: std::vec::Vec<u64> = (0..COUNT as u64).collect();
+    group.bench_function("std", |b| {
+        b.iter(|| std::rc::Rc::<[u64]>::from(black_box(&values[..])))
+    });
+    group.bench_function("crate", |b| {
+        b.iter(|| Rc::<[u64]>::from(black_box(&values[..])))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, new, clone_drop, upgrade, downgrade, slice);
+criterion_main!(benches);
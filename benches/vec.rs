@@ -0,0 +1,124 @@
+// Each benchmark pairs vec::Vec with std::vec::Vec doing the same work
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rust_samples::vec::vec::Vec;
+use std::hint::black_box;
+
+const SIZES: [usize; 3] = [16, 1024, 65536];
+
+fn push(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vec/push");
+    for size in SIZES {
+        group.bench_with_input(BenchmarkId::new("std", size), &size, |b, &size| {
+            b.iter(|| {
+                let mut vec = std::vec::Vec::new();
+                for value in 0..size {
+                    vec.push(black_box(value));
+                }
+                vec
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("crate", size), &size, |b, &size| {
+            b.iter(|| {
+                let mut vec = Vec::new();
+                for value in 0..size {
+                    vec.push(black_box(value));
+                }
+                vec
+            })
+        });
+    }
+    group.finish();
+}
+
+fn extend(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vec/extend");
+    for size in SIZES {
+        group.bench_with_input(BenchmarkId::new("std", size), &size, |b, &size| {
+            b.iter(|| {
+                let mut vec = std::vec::Vec::new();
+                vec.extend(black_box(0..size));
+                vec
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("crate", size), &size, |b, &size| {
+            b.iter(|| {
+                let mut vec = Vec::new();
+                vec.extend(black_box(0..size));
+                vec
+            })
+        });
+    }
+    group.finish();
+}
+
+fn iterate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vec/iterate");
+    for size in SIZES {
+        let expected: std::vec::Vec<usize> = (0..size).collect();
+        let actual: Vec<usize> = (0..size).collect();
+        group.bench_with_input(BenchmarkId::new("std", size), &expected, |b, vec| {
+            b.iter(|| black_box(vec).iter().sum::<usize>())
+        });
+        group.bench_with_input(BenchmarkId::new("crate", size), &actual, |b, vec| {
+            b.iter(|| black_box(vec).into_iter().sum::<usize>())
+        });
+    }
+    group.finish();
+}
+
+fn index(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vec/index");
+    for size in SIZES {
+        let expected: std::vec::Vec<usize> = (0..size).collect();
+        let actual: Vec<usize> = (0..size).collect();
+        // Strided access so the bounds check can't be hoisted out of the loop
+        group.bench_with_input(BenchmarkId::new("std", size), &expected, |b, vec| {
+            b.iter(|| {
+                let mut sum = 0;
+                for i in 0..size {
+                    sum += vec[black_box(i * 7 % size)];
+                }
+                sum
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("crate", size), &actual, |b, vec| {
+            b.iter(|| {
+                let mut sum = 0;
+                for i in 0..size {
+                    sum += vec[black_box(i * 7 % size)];
+                }
+                sum
+            })
+        });
+    }
+    group.finish();
+}
+
+fn insert_remove(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vec/insert_remove");
+    let size = 1024;
+    group.bench_function(BenchmarkId::new("std", size), |b| {
+        b.iter_batched_ref(
+            || (0..size).collect::<std::vec::Vec<usize>>(),
+            |vec| {
+                vec.insert(black_box(size / 2), 0);
+                vec.remove(black_box(size / 3))
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function(BenchmarkId::new("crate", size), |b| {
+        b.iter_batched_ref(
+            || (0..size).collect::<Vec<usize>>(),
+            |vec| {
+                vec.insert(black_box(size / 2), 0);
+                vec.remove(black_box(size / 3))
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, push, extend, iterate, index, insert_remove);
+criterion_main!(benches);
@@ -0,0 +1,116 @@
+// Differential fuzzing of either::Either combinators against Result, Right plays the role of Ok
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use rust_samples::either::either::{Either, IteratorExt};
+use rust_samples_fuzz::assert_no_leaks;
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    Flip,
+    Map(i32),
+    MapLeft(i32),
+    MapEither(i32, i32),
+    AndThen(i32),
+    OrElse(i32),
+    UnwrapOr(i32),
+}
+
+#[derive(Arbitrary, Debug)]
+struct Script {
+    start: Result<i32, i32>,
+    ops: Vec<Op>,
+    items: Vec<Result<u8, u8>>,
+}
+
+fn flip(result: Result<i32, i32>) -> Result<i32, i32> {
+    match result {
+        Ok(value) => Err(value),
+        Err(value) => Ok(value),
+    }
+}
+
+// Values below threshold switch sides
+fn split(value: i32, threshold: i32) -> Result<i32, i32> {
+    if value < threshold {
+        Err(value.wrapping_neg())
+    } else {
+        Ok(value.wrapping_mul(3))
+    }
+}
+
+fn run(script: &Script) {
+    let mut expected = script.start;
+    let mut actual = Either::from(script.start);
+
+    for op in &script.ops {
+        match *op {
+            Op::Flip => {
+                expected = flip(expected);
+                actual = actual.flip();
+            }
+            Op::Map(delta) => {
+                expected = expected.map(|value| value.wrapping_add(delta));
+                actual = actual.map(|value| value.wrapping_add(delta));
+            }
+            Op::MapLeft(delta) => {
+                expected = expected.map_err(|value| value.wrapping_add(delta));
+                actual = actual.map_left(|value| value.wrapping_add(delta));
+            }
+            Op::MapEither(left, right) => {
+                expected = expected
+                    .map(|value| value.wrapping_sub(right))
+                    .map_err(|value| value.wrapping_sub(left));
+                actual = actual.map_either(
+                    |value| value.wrapping_sub(left),
+                    |value| value.wrapping_sub(right),
+                );
+            }
+            Op::AndThen(threshold) => {
+                expected = expected.and_then(|value| split(value, threshold));
+                actual = actual.and_then(|value| Either::from(split(value, threshold)));
+            }
+            Op::OrElse(threshold) => {
+                expected = expected.or_else(|value| flip(split(value, threshold)));
+                actual = actual.or_else(|value| Either::from(flip(split(value, threshold))));
+            }
+            Op::UnwrapOr(default) => {
+                assert_eq!(actual.unwrap_or(default), expected.unwrap_or(default));
+            }
+        }
+
+        assert_eq!(Result::from(actual), expected);
+        assert_eq!(actual.is_left(), expected.is_err());
+        assert_eq!(actual.ok(), expected.ok());
+        assert_eq!(actual.left(), expected.err());
+        assert_eq!(actual.iter().count(), expected.iter().count());
+        assert_eq!(
+            actual.either(|value| value as i64 - 1, |value| value as i64 + 1),
+            expected.map_or_else(|value| value as i64 - 1, |value| value as i64 + 1)
+        );
+    }
+
+    let items = || script.items.iter().copied().map(Either::from);
+    let errors: Vec<u8> = script.items.iter().filter_map(|item| item.err()).collect();
+    let values: Vec<u8> = script.items.iter().filter_map(|item| item.ok()).collect();
+
+    let (lefts, rights): (Vec<u8>, Vec<u8>) = items().partition_either();
+    assert_eq!(lefts, errors);
+    assert_eq!(rights, values);
+
+    let all: Either<Vec<u8>, Vec<u8>> = items().collect_all_either();
+    if errors.is_empty() {
+        assert_eq!(all, Either::Right(values));
+    } else {
+        assert_eq!(all, Either::Left(errors));
+    }
+
+    let collected: Either<u8, Vec<u8>> = items().collect();
+    let expected: Result<Vec<u8>, u8> = script.items.iter().copied().collect();
+    assert_eq!(Result::from(collected), expected);
+}
+
+fuzz_target!(|script: Script| {
+    assert_no_leaks(|| run(&script));
+});
@@ -0,0 +1,168 @@
+// Differential fuzzing of rc::Rc and rc::Weak graphs against std::rc
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use rust_samples::rc::rc::{Rc, Weak};
+use rust_samples_fuzz::assert_no_leaks;
+use std::cell::RefCell;
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    New(u8),
+    NewWeak,
+    Clone(u8),
+    Downgrade(u8),
+    Upgrade(u8),
+    DropStrong(u8),
+    DropWeak(u8),
+    CloneWeak(u8),
+    // Links are weak, strong cycles would leak by design
+    Link(u8, u8),
+    Follow(u8),
+    RawRoundTrip(u8),
+}
+
+struct Node {
+    value: String,
+    next: RefCell<Weak<Node>>,
+}
+
+struct StdNode {
+    value: String,
+    next: RefCell<std::rc::Weak<StdNode>>,
+}
+
+#[derive(Default)]
+struct Graph {
+    strongs: Vec<(Rc<Node>, std::rc::Rc<StdNode>)>,
+    weaks: Vec<(Weak<Node>, std::rc::Weak<StdNode>)>,
+}
+
+impl Graph {
+    fn strong(&self, index: u8) -> Option<usize> {
+        (!self.strongs.is_empty()).then(|| index as usize % self.strongs.len())
+    }
+
+    fn weak(&self, index: u8) -> Option<usize> {
+        (!self.weaks.is_empty()).then(|| index as usize % self.weaks.len())
+    }
+
+    fn apply(&mut self, op: &Op) {
+        match *op {
+            Op::New(value) => self.strongs.push((
+                Rc::new(Node {
+                    value: value.to_string(),
+                    next: RefCell::new(Weak::new()),
+                }),
+                std::rc::Rc::new(StdNode {
+                    value: value.to_string(),
+                    next: RefCell::new(std::rc::Weak::new()),
+                }),
+            )),
+            Op::NewWeak => self.weaks.push((Weak::new(), std::rc::Weak::new())),
+            Op::Clone(index) => {
+                if let Some(i) = self.strong(index) {
+                    let (actual, expected) = &self.strongs[i];
+                    let pair = (actual.clone(), expected.clone());
+                    self.strongs.push(pair);
+                }
+            }
+            Op::Downgrade(index) => {
+                if let Some(i) = self.strong(index) {
+                    let (actual, expected) = &self.strongs[i];
+                    let pair = (Rc::downgrade(actual), std::rc::Rc::downgrade(expected));
+                    self.weaks.push(pair);
+                }
+            }
+            Op::Upgrade(index) => {
+                if let Some(i) = self.weak(index) {
+                    let (actual, expected) = &self.weaks[i];
+                    match (actual.upgrade(), expected.upgrade()) {
+                        (Some(actual), Some(expected)) => self.strongs.push((actual, expected)),
+                        (None, None) => {}
+                        _ => panic!("upgrade differs"),
+                    }
+                }
+            }
+            Op::DropStrong(index) => {
+                if let Some(i) = self.strong(index) {
+                    self.strongs.swap_remove(i);
+                }
+            }
+            Op::DropWeak(index) => {
+                if let Some(i) = self.weak(index) {
+                    self.weaks.swap_remove(i);
+                }
+            }
+            Op::CloneWeak(index) => {
+                if let Some(i) = self.weak(index) {
+                    let (actual, expected) = &self.weaks[i];
+                    let pair = (actual.clone(), expected.clone());
+                    self.weaks.push(pair);
+                }
+            }
+            Op::Link(from, to) => {
+                if let (Some(from), Some(to)) = (self.strong(from), self.strong(to)) {
+                    let (actual, expected) = &self.strongs[to];
+                    let pair = (Rc::downgrade(actual), std::rc::Rc::downgrade(expected));
+                    *self.strongs[from].0.next.borrow_mut() = pair.0;
+                    *self.strongs[from].1.next.borrow_mut() = pair.1;
+                }
+            }
+            Op::Follow(index) => {
+                if let Some(i) = self.strong(index) {
+                    let (actual, expected) = &self.strongs[i];
+                    let next = actual.next.borrow().clone();
+                    let expected_next = expected.next.borrow().clone();
+                    self.weaks.push((next, expected_next));
+                }
+            }
+            Op::RawRoundTrip(index) => {
+                if let Some(i) = self.strong(index) {
+                    let (actual, expected) = self.strongs.swap_remove(i);
+                    let actual = unsafe { Rc::from_raw(Rc::into_raw(actual)) };
+                    self.strongs.push((actual, expected));
+                }
+            }
+        }
+    }
+
+    fn check(&self) {
+        for (actual, expected) in &self.strongs {
+            assert_eq!(actual.value, expected.value);
+            assert_eq!(
+                Rc::strong_count(actual),
+                std::rc::Rc::strong_count(expected)
+            );
+            assert_eq!(Rc::weak_count(actual), std::rc::Rc::weak_count(expected));
+        }
+        for (actual, expected) in &self.weaks {
+            assert_eq!(actual.strong_count(), expected.strong_count());
+            // std reports no weak references once the value is gone
+            if expected.strong_count() > 0 {
+                assert_eq!(actual.weak_count(), expected.weak_count());
+            }
+            assert_eq!(
+                actual.upgrade().map(|node| node.value.clone()),
+                expected.upgrade().map(|node| node.value.clone())
+            );
+        }
+        for pair in self.strongs.windows(2) {
+            assert_eq!(
+                Rc::ptr_eq(&pair[0].0, &pair[1].0),
+                std::rc::Rc::ptr_eq(&pair[0].1, &pair[1].1)
+            );
+        }
+    }
+}
+
+fuzz_target!(|ops: Vec<Op>| {
+    assert_no_leaks(|| {
+        let mut graph = Graph::default();
+        for op in &ops {
+            graph.apply(op);
+            graph.check();
+        }
+    });
+});
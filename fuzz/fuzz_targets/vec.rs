@@ -0,0 +1,93 @@
+// Differential fuzzing of vec::Vec against std::vec::Vec
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use rust_samples::vec::vec::Vec;
+use rust_samples_fuzz::assert_no_leaks;
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    Push(u8),
+    Pop,
+    Insert(u8, u8),
+    Remove(u8),
+    Truncate(u8),
+    Index(u8),
+    Set(u8, u8),
+    Append(std::vec::Vec<u8>),
+    Extend(std::vec::Vec<u8>),
+    FromElem(u8, u8),
+}
+
+#[derive(Arbitrary, Debug)]
+struct Script {
+    capacity: u8,
+    ops: std::vec::Vec<Op>,
+}
+
+// Elements own heap memory, so lost or doubled drops are seen by the allocation tracker
+fn element(value: u8) -> String {
+    value.to_string()
+}
+
+fn run(script: &Script) {
+    let mut expected: std::vec::Vec<String> = std::vec::Vec::new();
+    let mut actual: Vec<String> = Vec::with_capacity(script.capacity as usize);
+
+    for op in &script.ops {
+        let len = expected.len();
+        match op {
+            Op::Push(value) => {
+                expected.push(element(*value));
+                actual.push(element(*value));
+            }
+            Op::Pop => assert_eq!(actual.pop(), expected.pop()),
+            Op::Insert(index, value) => {
+                let index = *index as usize % (len + 1);
+                expected.insert(index, element(*value));
+                actual.insert(index, element(*value));
+            }
+            Op::Remove(index) if len > 0 => {
+                let index = *index as usize % len;
+                assert_eq!(actual.remove(index), expected.remove(index));
+            }
+            Op::Truncate(new_len) => {
+                expected.truncate(*new_len as usize);
+                actual.truncate(*new_len as usize);
+            }
+            Op::Index(index) if len > 0 => {
+                let index = *index as usize % len;
+                assert_eq!(actual[index], expected[index]);
+            }
+            Op::Set(index, value) if len > 0 => {
+                let index = *index as usize % len;
+                expected[index] = element(*value);
+                actual[index] = element(*value);
+            }
+            Op::Append(values) => {
+                let mut other: Vec<String> = values.iter().copied().map(element).collect();
+                expected.extend(values.iter().copied().map(element));
+                actual.append(&mut other);
+                assert_eq!(other.len(), 0);
+            }
+            Op::Extend(values) => {
+                expected.extend(values.iter().copied().map(element));
+                actual.extend(values.iter().copied().map(element));
+            }
+            Op::FromElem(value, count) => {
+                expected = vec![element(*value); *count as usize];
+                actual = Vec::from_elem(element(*value), *count as usize);
+            }
+            Op::Remove(_) | Op::Index(_) | Op::Set(_, _) => {}
+        }
+
+        assert_eq!(actual.len(), expected.len());
+        assert!(actual.len() <= actual.allocated());
+        assert_eq!(&actual[0..actual.len()], &expected[..]);
+    }
+}
+
+fuzz_target!(|script: Script| {
+    assert_no_leaks(|| run(&script));
+});
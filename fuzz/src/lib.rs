@@ -0,0 +1,52 @@
+// Allocation tracker shared by fuzz targets, every heap allocation of a target goes through it
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub struct TrackingAllocator {
+    live: AtomicUsize,
+    bytes: AtomicUsize,
+}
+
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator {
+    live: AtomicUsize::new(0),
+    bytes: AtomicUsize::new(0),
+};
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            self.live.fetch_add(1, Ordering::Relaxed);
+            self.bytes.fetch_add(layout.size(), Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // More frees than allocations can only come from a double free,
+        // panicking here would allocate, so the process is aborted
+        if self.live.fetch_sub(1, Ordering::Relaxed) == 0 {
+            process::abort();
+        }
+        self.bytes.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+// (allocations, bytes) currently live
+fn snapshot() -> (usize, usize) {
+    (
+        ALLOCATOR.live.load(Ordering::Relaxed),
+        ALLOCATOR.bytes.load(Ordering::Relaxed),
+    )
+}
+
+// Runs f and panics when it leaves allocations behind or frees more than it allocated
+pub fn assert_no_leaks<F: FnOnce()>(f: F) {
+    let before = snapshot();
+    f();
+    let after = snapshot();
+    assert_eq!(before, after, "live (allocations, bytes) changed");
+}
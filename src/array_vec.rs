@@ -0,0 +1,600 @@
+pub mod array_vec {
+    use core::fmt::{self, Debug, Display, Formatter};
+    use core::iter::FusedIterator;
+    use core::mem::MaybeUninit;
+    use core::ops::{Bound, Deref, DerefMut, RangeBounds};
+    use core::ptr;
+    use core::slice;
+    use core::str;
+
+    // Holds up to N elements without allocating
+    pub struct ArrayVec<T, const N: usize> {
+        // Only first len elements are initialized
+        buffer: [MaybeUninit<T>; N],
+        len: usize,
+    }
+
+    impl<T, const N: usize> ArrayVec<T, N> {
+        pub const fn new() -> Self {
+            ArrayVec {
+                buffer: [const { MaybeUninit::uninit() }; N],
+                len: 0,
+            }
+        }
+
+        pub const fn len(&self) -> usize {
+            self.len
+        }
+
+        pub const fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        pub const fn capacity(&self) -> usize {
+            N
+        }
+
+        pub const fn is_full(&self) -> bool {
+            self.len == N
+        }
+
+        // Gives the value back when there is no room for it
+        pub fn try_push(&mut self, value: T) -> Result<(), T> {
+            if self.is_full() {
+                return Err(value);
+            }
+            self.buffer[self.len].write(value);
+            self.len += 1;
+            Ok(())
+        }
+
+        pub fn push(&mut self, value: T) {
+            if self.try_push(value).is_err() {
+                panic!("ArrayVec is full: the capacity is {}", N);
+            }
+        }
+
+        pub fn pop(&mut self) -> Option<T> {
+            if self.len == 0 {
+                None
+            } else {
+                self.len -= 1;
+                unsafe { Some(self.buffer[self.len].assume_init_read()) }
+            }
+        }
+
+        // Elements past len are dropped where they are, capacity is fixed anyway
+        pub fn truncate(&mut self, len: usize) {
+            if len < self.len {
+                let tail = ptr::slice_from_raw_parts_mut(
+                    unsafe { self.as_mut_ptr().add(len) },
+                    self.len - len,
+                );
+                // ArrayVec::drop goes by len, it must not see the tail if an element drop panics
+                self.len = len;
+                unsafe { ptr::drop_in_place(tail) };
+            }
+        }
+
+        pub fn clear(&mut self) {
+            self.truncate(0);
+        }
+
+        pub fn as_slice(&self) -> &[T] {
+            unsafe { slice::from_raw_parts(self.as_ptr(), self.len) }
+        }
+
+        pub fn as_mut_slice(&mut self) -> &mut [T] {
+            unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.len) }
+        }
+
+        // Removes the range, remaining elements are shifted left when Drain is dropped
+        pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T, N> {
+            let start = match range.start_bound() {
+                Bound::Included(&start) => Some(start),
+                Bound::Excluded(&start) => start.checked_add(1),
+                Bound::Unbounded => Some(0),
+            };
+            let end = match range.end_bound() {
+                Bound::Included(&end) => end.checked_add(1),
+                Bound::Excluded(&end) => Some(end),
+                Bound::Unbounded => Some(self.len),
+            };
+            let (start, end) = match (start, end) {
+                (Some(start), Some(end)) if start <= end && end <= self.len => (start, end),
+                (Some(start), Some(end)) => panic!(
+                    "range {}..{} out of bounds for length {}",
+                    start, end, self.len
+                ),
+                // Bound at usize::MAX has no exclusive form, so bounds are shown as given
+                _ => panic!(
+                    "range {:?}..{:?} out of bounds for length {}",
+                    range.start_bound(),
+                    range.end_bound(),
+                    self.len
+                ),
+            };
+            let tail_len = self.len - end;
+            // Drained and tail elements belong to Drain, leaking it leaks them instead of dropping twice
+            self.len = start;
+            Drain {
+                vec: self,
+                front: start,
+                back: end,
+                tail_start: end,
+                tail_len,
+            }
+        }
+
+        fn as_ptr(&self) -> *const T {
+            self.buffer.as_ptr() as *const T
+        }
+
+        fn as_mut_ptr(&mut self) -> *mut T {
+            self.buffer.as_mut_ptr() as *mut T
+        }
+    }
+
+    impl<T, const N: usize> Default for ArrayVec<T, N> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<T, const N: usize> Drop for ArrayVec<T, N> {
+        fn drop(&mut self) {
+            self.clear();
+        }
+    }
+
+    impl<T, const N: usize> Deref for ArrayVec<T, N> {
+        type Target = [T];
+
+        fn deref(&self) -> &Self::Target {
+            self.as_slice()
+        }
+    }
+
+    impl<T, const N: usize> DerefMut for ArrayVec<T, N> {
+        fn deref_mut(&mut self) -> &mut Self::Target {
+            self.as_mut_slice()
+        }
+    }
+
+    impl<T: Clone, const N: usize> Clone for ArrayVec<T, N> {
+        fn clone(&self) -> Self {
+            self.iter().cloned().collect()
+        }
+    }
+
+    impl<T: Debug, const N: usize> Debug for ArrayVec<T, N> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            Debug::fmt(self.as_slice(), f)
+        }
+    }
+
+    impl<T: PartialEq, const N: usize> PartialEq for ArrayVec<T, N> {
+        fn eq(&self, other: &Self) -> bool {
+            self.as_slice() == other.as_slice()
+        }
+    }
+
+    impl<T: Eq, const N: usize> Eq for ArrayVec<T, N> {}
+
+    impl<'a, T, const N: usize> IntoIterator for &'a ArrayVec<T, N> {
+        type Item = &'a T;
+        type IntoIter = slice::Iter<'a, T>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.as_slice().iter()
+        }
+    }
+
+    // Panics when the iterator has more than N items
+    impl<T, const N: usize> FromIterator<T> for ArrayVec<T, N> {
+        fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+            let mut result = ArrayVec::new();
+            result.extend(iter);
+            result
+        }
+    }
+
+    impl<T, const N: usize> Extend<T> for ArrayVec<T, N> {
+        fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+            for value in iter {
+                self.push(value);
+            }
+        }
+    }
+
+    pub struct Drain<'a, T, const N: usize> {
+        vec: &'a mut ArrayVec<T, N>,
+        // Not yet yielded elements are front..back
+        front: usize,
+        back: usize,
+        // Elements after the drained range
+        tail_start: usize,
+        tail_len: usize,
+    }
+
+    impl<T, const N: usize> Iterator for Drain<'_, T, N> {
+        type Item = T;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.front == self.back {
+                None
+            } else {
+                self.front += 1;
+                unsafe { Some(self.vec.as_ptr().add(self.front - 1).read()) }
+            }
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let len = self.back - self.front;
+            (len, Some(len))
+        }
+    }
+
+    impl<T, const N: usize> DoubleEndedIterator for Drain<'_, T, N> {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            if self.front == self.back {
+                None
+            } else {
+                self.back -= 1;
+                unsafe { Some(self.vec.as_ptr().add(self.back).read()) }
+            }
+        }
+    }
+
+    impl<T, const N: usize> ExactSizeIterator for Drain<'_, T, N> {}
+
+    impl<T, const N: usize> FusedIterator for Drain<'_, T, N> {}
+
+    impl<T, const N: usize> Drop for Drain<'_, T, N> {
+        fn drop(&mut self) {
+            unsafe {
+                let elements = self.vec.as_mut_ptr();
+                let remaining =
+                    ptr::slice_from_raw_parts_mut(elements.add(self.front), self.back - self.front);
+                self.front = self.back;
+                ptr::drop_in_place(remaining);
+                let start = self.vec.len;
+                elements
+                    .add(self.tail_start)
+                    .copy_to(elements.add(start), self.tail_len);
+                self.vec.len = start + self.tail_len;
+            }
+        }
+    }
+
+    // UTF-8 string of up to N bytes without allocating
+    #[derive(Clone, Default, PartialEq, Eq)]
+    pub struct ArrayString<const N: usize> {
+        bytes: ArrayVec<u8, N>,
+    }
+
+    impl<const N: usize> ArrayString<N> {
+        pub const fn new() -> Self {
+            ArrayString {
+                bytes: ArrayVec::new(),
+            }
+        }
+
+        pub const fn len(&self) -> usize {
+            self.bytes.len()
+        }
+
+        pub const fn is_empty(&self) -> bool {
+            self.bytes.is_empty()
+        }
+
+        pub const fn capacity(&self) -> usize {
+            N
+        }
+
+        // Gives the string back when it doesn't fit, nothing is appended then
+        pub fn try_push_str<'a>(&mut self, value: &'a str) -> Result<(), &'a str> {
+            if value.len() > N - self.len() {
+                return Err(value);
+            }
+            self.bytes.extend(value.bytes());
+            Ok(())
+        }
+
+        pub fn push_str(&mut self, value: &str) {
+            if self.try_push_str(value).is_err() {
+                panic!(
+                    "ArrayString is full: the capacity is {} but the length would be {}",
+                    N,
+                    self.len() + value.len()
+                );
+            }
+        }
+
+        pub fn try_push(&mut self, value: char) -> Result<(), char> {
+            self.try_push_str(value.encode_utf8(&mut [0; 4]))
+                .map_err(|_| value)
+        }
+
+        pub fn push(&mut self, value: char) {
+            self.push_str(value.encode_utf8(&mut [0; 4]));
+        }
+
+        pub fn pop(&mut self) -> Option<char> {
+            let value = self.chars().next_back()?;
+            self.bytes.truncate(self.len() - value.len_utf8());
+            Some(value)
+        }
+
+        // Panics when len is not on a char boundary
+        pub fn truncate(&mut self, len: usize) {
+            if len < self.len() {
+                assert!(
+                    self.is_char_boundary(len),
+                    "new length {} is not a char boundary",
+                    len
+                );
+                self.bytes.truncate(len);
+            }
+        }
+
+        pub fn clear(&mut self) {
+            self.bytes.clear();
+        }
+
+        pub fn as_str(&self) -> &str {
+            // Bytes only ever come from whole strs
+            unsafe { str::from_utf8_unchecked(&self.bytes) }
+        }
+    }
+
+    impl<const N: usize> Deref for ArrayString<N> {
+        type Target = str;
+
+        fn deref(&self) -> &Self::Target {
+            self.as_str()
+        }
+    }
+
+    impl<'a, const N: usize> TryFrom<&'a str> for ArrayString<N> {
+        type Error = &'a str;
+
+        fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+            let mut result = ArrayString::new();
+            result.try_push_str(value)?;
+            Ok(result)
+        }
+    }
+
+    impl<const N: usize> PartialEq<str> for ArrayString<N> {
+        fn eq(&self, other: &str) -> bool {
+            self.as_str() == other
+        }
+    }
+
+    impl<const N: usize> PartialEq<&str> for ArrayString<N> {
+        fn eq(&self, other: &&str) -> bool {
+            self.as_str() == *other
+        }
+    }
+
+    impl<const N: usize> Debug for ArrayString<N> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            Debug::fmt(self.as_str(), f)
+        }
+    }
+
+    impl<const N: usize> Display for ArrayString<N> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            Display::fmt(self.as_str(), f)
+        }
+    }
+
+    // Piece that doesn't fit is skipped and fails the write,
+    // pieces of the same write! before it stay appended
+    impl<const N: usize> fmt::Write for ArrayString<N> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.try_push_str(s).map_err(|_| fmt::Error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array_vec::array_vec::{ArrayString, ArrayVec};
+    use crate::tracked::tracked::Tracked;
+    use std::cell::Cell;
+    use std::fmt::Write;
+    use std::ops::Bound;
+
+    #[test]
+    fn should_push_up_to_capacity() {
+        let mut vec: ArrayVec<i32, 3> = ArrayVec::new();
+
+        vec.push(1);
+        vec.push(2);
+
+        assert_eq!(vec.try_push(3), Ok(()));
+        assert_eq!(vec.try_push(4), Err(4));
+        assert_eq!(vec.is_full(), true);
+        assert_eq!(&vec[..], &[1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "ArrayVec is full: the capacity is 1")]
+    fn should_panic_on_push_when_full() {
+        let mut vec: ArrayVec<i32, 1> = ArrayVec::new();
+        vec.push(1);
+
+        vec.push(2);
+    }
+
+    #[test]
+    fn should_reject_push_without_capacity() {
+        let mut vec: ArrayVec<String, 0> = ArrayVec::new();
+
+        assert_eq!(vec.try_push(String::from("a")), Err(String::from("a")));
+        assert_eq!(vec.pop(), None);
+    }
+
+    #[test]
+    fn should_act_as_slice() {
+        let mut vec: ArrayVec<i32, 4> = [3, 1, 2].into_iter().collect();
+
+        vec.sort();
+        vec[2] = 5;
+
+        assert_eq!(vec.pop(), Some(5));
+        assert_eq!(vec.contains(&2), true);
+        assert_eq!(format!("{:?}", vec), "[1, 2]");
+    }
+
+    #[test]
+    fn should_drain_middle_range() {
+        let mut vec: ArrayVec<i32, 8> = (1..=6).collect();
+
+        let drained: Vec<i32> = vec.drain(1..4).collect();
+
+        assert_eq!(drained, [2, 3, 4]);
+        assert_eq!(&vec[..], &[1, 5, 6]);
+    }
+
+    #[test]
+    fn should_drain_from_both_ends() {
+        let mut vec: ArrayVec<i32, 8> = (1..=6).collect();
+
+        let mut drain = vec.drain(..=3);
+
+        assert_eq!(drain.len(), 4);
+        assert_eq!(drain.next_back(), Some(4));
+        assert_eq!(drain.next(), Some(1));
+        assert_eq!(drain.len(), 2);
+        drop(drain);
+        assert_eq!(&vec[..], &[5, 6]);
+    }
+
+    #[test]
+    fn should_drop_elements_left_in_drain() {
+        let drops = Cell::new(0);
+        let mut vec: ArrayVec<Tracked, 4> = ArrayVec::new();
+        for _ in 0..4 {
+            vec.push(Tracked(&drops));
+        }
+
+        let mut drain = vec.drain(1..3);
+        drop(drain.next());
+        drop(drain);
+        assert_eq!(drops.get(), 2);
+        assert_eq!(vec.len(), 2);
+        drop(vec);
+
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "range 2..5 out of bounds for length 3")]
+    fn should_panic_on_drain_past_end() {
+        let mut vec: ArrayVec<i32, 8> = (1..=3).collect();
+
+        vec.drain(2..5);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "range Included(0)..Included(18446744073709551615) out of bounds for length 3"
+    )]
+    fn should_panic_on_drain_to_max() {
+        let mut vec: ArrayVec<i32, 8> = (1..=3).collect();
+
+        vec.drain(0..=usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds for length 3")]
+    fn should_panic_on_drain_after_max() {
+        let mut vec: ArrayVec<i32, 8> = (1..=3).collect();
+
+        vec.drain((Bound::Excluded(usize::MAX), Bound::Unbounded));
+    }
+
+    #[test]
+    fn should_clone_and_truncate() {
+        let vec: ArrayVec<String, 4> = ["a", "b", "c"].into_iter().map(String::from).collect();
+
+        let mut result = vec.clone();
+        result.truncate(1);
+
+        assert_eq!(vec.len(), 3);
+        assert_eq!(&result[..], &[String::from("a")]);
+    }
+
+    #[test]
+    fn should_push_strings_up_to_capacity() {
+        let mut text: ArrayString<8> = ArrayString::new();
+
+        text.push_str("héllo");
+        text.push('!');
+
+        assert_eq!(text, "héllo!");
+        assert_eq!(text.len(), 7);
+        assert_eq!(text.try_push('é'), Err('é'));
+        assert_eq!(text.try_push_str("ab"), Err("ab"));
+        assert_eq!(text.try_push_str("a"), Ok(()));
+        assert_eq!(text, "héllo!a");
+    }
+
+    #[test]
+    #[should_panic(expected = "ArrayString is full: the capacity is 2 but the length would be 3")]
+    fn should_panic_on_push_str_when_full() {
+        let mut text: ArrayString<2> = ArrayString::new();
+
+        text.push_str("abc");
+    }
+
+    #[test]
+    fn should_pop_whole_chars() {
+        let mut text: ArrayString<8> = ArrayString::try_from("aé").unwrap();
+
+        assert_eq!(text.pop(), Some('é'));
+        assert_eq!(text.pop(), Some('a'));
+        assert_eq!(text.pop(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "new length 2 is not a char boundary")]
+    fn should_panic_on_truncate_inside_char() {
+        let mut text: ArrayString<8> = ArrayString::try_from("aé").unwrap();
+
+        text.truncate(2);
+    }
+
+    #[test]
+    fn should_format_into_string() {
+        let mut text: ArrayString<8> = ArrayString::new();
+
+        write!(text, "{}-{}", 12, 34).unwrap();
+
+        assert_eq!(text.to_string(), "12-34");
+        assert_eq!(write!(text, "{}", 5678), Err(std::fmt::Error));
+        assert_eq!(format!("{:?}", text), "\"12-34\"");
+    }
+
+    #[test]
+    fn should_keep_pieces_written_before_failure() {
+        struct Pieces;
+
+        impl std::fmt::Display for Pieces {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("1-")?;
+                f.write_str("2345")
+            }
+        }
+
+        let mut text: ArrayString<4> = ArrayString::new();
+
+        let result = write!(text, "{}", Pieces);
+
+        assert_eq!(result, Err(std::fmt::Error));
+        assert_eq!(text.to_string(), "1-");
+    }
+}
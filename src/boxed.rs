@@ -1,18 +1,60 @@
 pub mod boxed {
-    use std::alloc::Layout;
-    use std::alloc::{alloc, dealloc};
-    use std::fmt::{Debug, Formatter};
-    use std::ops::{Deref, DerefMut};
+    use alloc::alloc::Layout;
+    use alloc::alloc::{alloc, dealloc, handle_alloc_error};
+    use core::fmt::{Debug, Formatter};
+    use core::marker::PhantomData;
+    use core::mem;
+    use core::ops::{Deref, DerefMut};
+    use core::ptr::{self, NonNull};
+
+    /// ```compile_fail,E0277
+    /// fn assert_send<T: Send>() {}
+    /// assert_send::<rust_samples::boxed::boxed::Box<rust_samples::rc::rc::Rc<i32>>>();
+    /// ```
+    pub struct Box<T> {
+        ptr: NonNull<T>,
+        // Box owns its value, dropping it may drop T
+        phantom: PhantomData<T>,
+    }
+
+    // Box is an exclusive owner, so thread safety is that of T
+    unsafe impl<T: Send> Send for Box<T> {}
 
-    pub struct Box<T>(*mut T);
+    unsafe impl<T: Sync> Sync for Box<T> {}
 
     impl<T> Box<T> {
         pub fn new(value: T) -> Self {
             unsafe {
                 let layout = Self::layout();
-                let ptr = alloc(layout) as *mut T;
-                *ptr = value;
-                Box(ptr)
+                // Zero-sized values are never allocated
+                let ptr = if layout.size() == 0 {
+                    NonNull::dangling()
+                } else {
+                    NonNull::new(alloc(layout) as *mut T)
+                        .unwrap_or_else(|| handle_alloc_error(layout))
+                };
+                ptr.write(value);
+                Box {
+                    ptr,
+                    phantom: PhantomData,
+                }
+            }
+        }
+
+        // Moves value out and releases the allocation
+        pub fn into_inner(boxed: Self) -> T {
+            unsafe {
+                let value = boxed.ptr.read();
+                boxed.release();
+                mem::forget(boxed);
+                value
+            }
+        }
+
+        unsafe fn release(&self) {
+            let layout = Self::layout();
+            if layout.size() != 0 {
+                dealloc(self.ptr.as_ptr() as *mut u8, layout);
             }
         }
 
@@ -24,14 +66,15 @@ pub mod boxed {
     impl<T> Drop for Box<T> {
         fn drop(&mut self) {
             unsafe {
-                dealloc(self.0 as *mut u8, Box::<T>::layout());
+                ptr::drop_in_place(self.ptr.as_ptr());
+                self.release();
             }
         }
     }
 
     impl<T: PartialEq> PartialEq<Self> for Box<T> {
         fn eq(&self, other: &Self) -> bool {
-            unsafe { *self.0 == *other.0 }
+            unsafe { *self.ptr.as_ptr() == *other.ptr.as_ptr() }
         }
     }
 
@@ -41,19 +84,33 @@ pub mod boxed {
         type Target = T;
 
         fn deref(&self) -> &Self::Target {
-            unsafe { &*self.0 }
+            unsafe { self.ptr.as_ref() }
         }
     }
 
     impl<T> DerefMut for Box<T> {
         fn deref_mut(&mut self) -> &mut Self::Target {
-            unsafe { &mut *self.0 }
+            unsafe { self.ptr.as_mut() }
         }
     }
 
     impl<T: Debug> Debug for Box<T> {
-        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            self.0.fmt(f)
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            self.ptr.fmt(f)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<T: serde::Serialize> serde::Serialize for Box<T> {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            (**self).serialize(serializer)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Box<T> {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            T::deserialize(deserializer).map(Box::new)
         }
     }
 }
@@ -93,4 +150,47 @@ mod tests {
 
         assert_eq!(*uat, 7);
     }
+
+    #[test]
+    fn should_move_value_out() {
+        let uat = boxed::Box::new(String::from("Hello"));
+
+        let result = boxed::Box::into_inner(uat);
+
+        assert_eq!(result, "Hello");
+    }
+
+    #[test]
+    fn should_move_to_another_thread() {
+        let uat = boxed::Box::new(String::from("Hello"));
+
+        let result = std::thread::spawn(move || boxed::Box::into_inner(uat))
+            .join()
+            .unwrap();
+
+        assert_eq!(result, "Hello");
+    }
+
+    #[test]
+    fn should_be_covariant() {
+        fn shorten<'a>(uat: boxed::Box<&'static str>) -> boxed::Box<&'a str> {
+            uat
+        }
+
+        let uat = shorten(boxed::Box::new("Hello"));
+
+        assert_eq!(*uat, "Hello");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_round_trip_through_json() {
+        let uat = boxed::Box::new(String::from("Hello"));
+
+        let json = serde_json::to_string(&uat).unwrap();
+        let result: boxed::Box<String> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(json, "\"Hello\"");
+        assert_eq!(result, uat);
+    }
 }
@@ -1,20 +1,24 @@
 pub mod either {
+    use core::error::Error;
+    use core::fmt::{self, Display, Formatter};
+    use core::future::Future;
+    use core::iter::FusedIterator;
+    use core::ops::{Deref, DerefMut};
+    use core::pin::Pin;
+    use core::task::{Context, Poll};
+    #[cfg(feature = "std")]
+    use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
+
     // Left -> Error
     // Right -> Correct value
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub enum Either<L, R> {
         Left(L),
         Right(R),
     }
 
     impl<L, R> Either<L, R> {
-        pub fn left(value: L) -> Self {
-            Either::Left(value)
-        }
-
-        pub fn right(value: R) -> Self {
-            Either::Right(value)
-        }
-
         pub fn is_left(&self) -> bool {
             match self {
                 Either::Left(_) => true,
@@ -29,6 +33,172 @@ pub mod either {
             }
         }
 
+        pub fn as_ref(&self) -> Either<&L, &R> {
+            match self {
+                Either::Left(value) => Either::Left(value),
+                Either::Right(value) => Either::Right(value),
+            }
+        }
+
+        pub fn as_mut(&mut self) -> Either<&mut L, &mut R> {
+            match self {
+                Either::Left(value) => Either::Left(value),
+                Either::Right(value) => Either::Right(value),
+            }
+        }
+
+        pub fn as_deref(&self) -> Either<&L::Target, &R::Target>
+        where
+            L: Deref,
+            R: Deref,
+        {
+            match self {
+                Either::Left(value) => Either::Left(value.deref()),
+                Either::Right(value) => Either::Right(value.deref()),
+            }
+        }
+
+        pub fn as_deref_mut(&mut self) -> Either<&mut L::Target, &mut R::Target>
+        where
+            L: DerefMut,
+            R: DerefMut,
+        {
+            match self {
+                Either::Left(value) => Either::Left(value.deref_mut()),
+                Either::Right(value) => Either::Right(value.deref_mut()),
+            }
+        }
+
+        // Pinned Either never moves its active value, so pinning projects to both sides
+        pub fn as_pin_ref(self: Pin<&Self>) -> Either<Pin<&L>, Pin<&R>> {
+            unsafe {
+                match self.get_ref() {
+                    Either::Left(value) => Either::Left(Pin::new_unchecked(value)),
+                    Either::Right(value) => Either::Right(Pin::new_unchecked(value)),
+                }
+            }
+        }
+
+        pub fn as_pin_mut(self: Pin<&mut Self>) -> Either<Pin<&mut L>, Pin<&mut R>> {
+            unsafe {
+                match self.get_unchecked_mut() {
+                    Either::Left(value) => Either::Left(Pin::new_unchecked(value)),
+                    Either::Right(value) => Either::Right(Pin::new_unchecked(value)),
+                }
+            }
+        }
+
+        pub fn left(self) -> Option<L> {
+            match self {
+                Either::Left(value) => Some(value),
+                Either::Right(_) => None,
+            }
+        }
+
+        pub fn right(self) -> Option<R> {
+            match self {
+                Either::Left(_) => None,
+                Either::Right(value) => Some(value),
+            }
+        }
+
+        // Same as right(), named after Result::ok
+        pub fn ok(self) -> Option<R> {
+            self.right()
+        }
+
+        // Same as left(), named after Result::err
+        pub fn err(self) -> Option<L> {
+            self.left()
+        }
+
+        pub fn ok_or(option: Option<R>, error: L) -> Self {
+            match option {
+                Some(value) => Either::Right(value),
+                None => Either::Left(error),
+            }
+        }
+
+        pub fn ok_or_else<F>(option: Option<R>, f: F) -> Self
+        where
+            F: FnOnce() -> L,
+        {
+            match option {
+                Some(value) => Either::Right(value),
+                None => Either::Left(f()),
+            }
+        }
+
+        pub fn map<F, S>(self, f: F) -> Either<L, S>
+        where
+            F: FnOnce(R) -> S,
+        {
+            match self {
+                Either::Left(value) => Either::Left(value),
+                Either::Right(value) => Either::Right(f(value)),
+            }
+        }
+
+        pub fn map_left<F, M>(self, f: F) -> Either<M, R>
+        where
+            F: FnOnce(L) -> M,
+        {
+            match self {
+                Either::Left(value) => Either::Left(f(value)),
+                Either::Right(value) => Either::Right(value),
+            }
+        }
+
+        pub fn map_either<F, G, M, S>(self, f: F, g: G) -> Either<M, S>
+        where
+            F: FnOnce(L) -> M,
+            G: FnOnce(R) -> S,
+        {
+            match self {
+                Either::Left(value) => Either::Left(f(value)),
+                Either::Right(value) => Either::Right(g(value)),
+            }
+        }
+
+        pub fn and_then<F, S>(self, f: F) -> Either<L, S>
+        where
+            F: FnOnce(R) -> Either<L, S>,
+        {
+            match self {
+                Either::Left(value) => Either::Left(value),
+                Either::Right(value) => f(value),
+            }
+        }
+
+        pub fn or_else<F, M>(self, f: F) -> Either<M, R>
+        where
+            F: FnOnce(L) -> Either<M, R>,
+        {
+            match self {
+                Either::Left(value) => f(value),
+                Either::Right(value) => Either::Right(value),
+            }
+        }
+
+        // Reduces both sides to a single value
+        pub fn either<F, G, T>(self, f: F, g: G) -> T
+        where
+            F: FnOnce(L) -> T,
+            G: FnOnce(R) -> T,
+        {
+            match self {
+                Either::Left(value) => f(value),
+                Either::Right(value) => g(value),
+            }
+        }
+
+        pub fn flip(self) -> Either<R, L> {
+            match self {
+                Either::Left(value) => Either::Right(value),
+                Either::Right(value) => Either::Left(value),
+            }
+        }
+
         // This function takes ownership of self
         pub fn unwrap(self) -> R {
             match self {
@@ -38,107 +208,1304 @@ pub mod either {
                 Either::Right(value) => value,
             }
         }
+
+        pub fn unwrap_left(self) -> L {
+            match self {
+                Either::Left(value) => value,
+                Either::Right(_) => {
+                    panic!("Called Either::unwrap_left() on Right value!")
+                }
+            }
+        }
+
+        pub fn expect(self, msg: &str) -> R {
+            match self {
+                Either::Left(_) => panic!("{}", msg),
+                Either::Right(value) => value,
+            }
+        }
+
+        pub fn expect_left(self, msg: &str) -> L {
+            match self {
+                Either::Left(value) => value,
+                Either::Right(_) => panic!("{}", msg),
+            }
+        }
+
+        pub fn unwrap_or(self, default: R) -> R {
+            match self {
+                Either::Left(_) => default,
+                Either::Right(value) => value,
+            }
+        }
+
+        pub fn unwrap_or_else<F>(self, f: F) -> R
+        where
+            F: FnOnce(L) -> R,
+        {
+            match self {
+                Either::Left(value) => f(value),
+                Either::Right(value) => value,
+            }
+        }
+
+        pub fn unwrap_or_default(self) -> R
+        where
+            R: Default,
+        {
+            match self {
+                Either::Left(_) => R::default(),
+                Either::Right(value) => value,
+            }
+        }
     }
 
-    pub enum EitherIterator<'a, R> {
-        Ref(&'a R),
-        None,
+    impl<L: Clone, R: Clone> Either<&L, &R> {
+        pub fn cloned(self) -> Either<L, R> {
+            self.map_either(L::clone, R::clone)
+        }
+    }
+
+    impl<L: Copy, R: Copy> Either<&L, &R> {
+        pub fn copied(self) -> Either<L, R> {
+            self.map_either(|value| *value, |value| *value)
+        }
+    }
+
+    impl<L: Clone, R: Clone> Either<&mut L, &mut R> {
+        pub fn cloned(self) -> Either<L, R> {
+            self.map_either(|value| value.clone(), |value| value.clone())
+        }
+    }
+
+    impl<L: Copy, R: Copy> Either<&mut L, &mut R> {
+        pub fn copied(self) -> Either<L, R> {
+            self.map_either(|value| *value, |value| *value)
+        }
+    }
+
+    impl<L, R> Either<L, Option<R>> {
+        pub fn transpose(self) -> Option<Either<L, R>> {
+            match self {
+                Either::Left(value) => Some(Either::Left(value)),
+                Either::Right(Some(value)) => Some(Either::Right(value)),
+                Either::Right(None) => None,
+            }
+        }
+    }
+
+    impl<L, R> From<Result<R, L>> for Either<L, R> {
+        fn from(value: Result<R, L>) -> Self {
+            match value {
+                Ok(value) => Either::Right(value),
+                Err(error) => Either::Left(error),
+            }
+        }
+    }
+
+    impl<L, R> From<Either<L, R>> for Result<R, L> {
+        fn from(value: Either<L, R>) -> Self {
+            match value {
+                Either::Left(error) => Err(error),
+                Either::Right(value) => Ok(value),
+            }
+        }
+    }
+
+    impl<L: Display, R: Display> Display for Either<L, R> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            match self {
+                Either::Left(value) => value.fmt(f),
+                Either::Right(value) => value.fmt(f),
+            }
+        }
     }
 
-    impl<'a, T> Iterator for EitherIterator<'a, T> {
-        type Item = &'a T;
+    impl<L: Error, R: Error> Error for Either<L, R> {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                Either::Left(error) => error.source(),
+                Either::Right(error) => error.source(),
+            }
+        }
+    }
+
+    // Either of two iterators, readers, writers or futures acts as the active one,
+    // so functions can return one of two concrete types without boxing
+    impl<L, R> Iterator for Either<L, R>
+    where
+        L: Iterator,
+        R: Iterator<Item = L::Item>,
+    {
+        type Item = L::Item;
 
         fn next(&mut self) -> Option<Self::Item> {
             match self {
-                EitherIterator::Ref(value) => {
-                    let result = Some(*value);
-                    *self = EitherIterator::None;
-                    result
-                }
-                EitherIterator::None => None,
+                Either::Left(iter) => iter.next(),
+                Either::Right(iter) => iter.next(),
+            }
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            match self {
+                Either::Left(iter) => iter.size_hint(),
+                Either::Right(iter) => iter.size_hint(),
             }
         }
     }
 
-    impl<'a, L, R> IntoIterator for &'a Either<L, R> {
-        type Item = &'a R;
-        type IntoIter = EitherIterator<'a, R>;
+    impl<L, R> DoubleEndedIterator for Either<L, R>
+    where
+        L: DoubleEndedIterator,
+        R: DoubleEndedIterator<Item = L::Item>,
+    {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            match self {
+                Either::Left(iter) => iter.next_back(),
+                Either::Right(iter) => iter.next_back(),
+            }
+        }
+    }
 
-        fn into_iter(self) -> Self::IntoIter {
-            match &self {
-                Either::Left(_) => EitherIterator::None,
-                Either::Right(value) => EitherIterator::Ref(value),
+    impl<L, R> ExactSizeIterator for Either<L, R>
+    where
+        L: ExactSizeIterator,
+        R: ExactSizeIterator<Item = L::Item>,
+    {
+    }
+
+    impl<L, R> FusedIterator for Either<L, R>
+    where
+        L: FusedIterator,
+        R: FusedIterator<Item = L::Item>,
+    {
+    }
+
+    #[cfg(feature = "std")]
+    impl<L: Read, R: Read> Read for Either<L, R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self {
+                Either::Left(reader) => reader.read(buf),
+                Either::Right(reader) => reader.read(buf),
             }
         }
     }
 
-    impl<R> FromIterator<R> for Either<(), R> {
-        fn from_iter<T: IntoIterator<Item = R>>(iter: T) -> Self {
-            let mut iterator = iter.into_iter();
-            match iterator.next() {
-                None => Either::Left(()),
-                Some(value) => Either::Right(value),
+    #[cfg(feature = "std")]
+    impl<L: BufRead, R: BufRead> BufRead for Either<L, R> {
+        fn fill_buf(&mut self) -> io::Result<&[u8]> {
+            match self {
+                Either::Left(reader) => reader.fill_buf(),
+                Either::Right(reader) => reader.fill_buf(),
+            }
+        }
+
+        fn consume(&mut self, amt: usize) {
+            match self {
+                Either::Left(reader) => reader.consume(amt),
+                Either::Right(reader) => reader.consume(amt),
             }
         }
     }
 
-    #[macro_export]
-    macro_rules! try_either {
-        ($expr:expr) => {
-            match $expr {
+    #[cfg(feature = "std")]
+    impl<L: Write, R: Write> Write for Either<L, R> {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            match self {
+                Either::Left(writer) => writer.write(buf),
+                Either::Right(writer) => writer.write(buf),
+            }
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            match self {
+                Either::Left(writer) => writer.flush(),
+                Either::Right(writer) => writer.flush(),
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl<L: Seek, R: Seek> Seek for Either<L, R> {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            match self {
+                Either::Left(seeker) => seeker.seek(pos),
+                Either::Right(seeker) => seeker.seek(pos),
+            }
+        }
+    }
+
+    impl<L: fmt::Write, R: fmt::Write> fmt::Write for Either<L, R> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            match self {
+                Either::Left(writer) => writer.write_str(s),
+                Either::Right(writer) => writer.write_str(s),
+            }
+        }
+    }
+
+    impl<L, R> Deref for Either<L, R>
+    where
+        L: Deref,
+        R: Deref<Target = L::Target>,
+    {
+        type Target = L::Target;
+
+        fn deref(&self) -> &Self::Target {
+            match self {
+                Either::Left(value) => value,
                 Either::Right(value) => value,
-                Either::Left(error) => return Either::left(error),
             }
-        };
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::either::either::Either;
-    use crate::try_either;
+    impl<L, R> DerefMut for Either<L, R>
+    where
+        L: DerefMut,
+        R: DerefMut<Target = L::Target>,
+    {
+        fn deref_mut(&mut self) -> &mut Self::Target {
+            match self {
+                Either::Left(value) => value,
+                Either::Right(value) => value,
+            }
+        }
+    }
 
-    #[test]
-    fn should_exit_on_left() {
-        let func = || -> Either<i32, String> {
-            try_either!(Either::left(15));
-            Either::right(String::from("Hello"))
-        };
+    impl<L, R> Future for Either<L, R>
+    where
+        L: Future,
+        R: Future<Output = L::Output>,
+    {
+        type Output = L::Output;
 
-        let result = func();
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            match self.as_pin_mut() {
+                Either::Left(future) => future.poll(cx),
+                Either::Right(future) => future.poll(cx),
+            }
+        }
+    }
 
-        assert_eq!(result.is_left(), true)
+    // Yields the value of one side at most once
+    /// There is no `IntoIterator` for `Either` or `&mut Either`: it would overlap with
+    /// `Iterator for Either`, which already gives both an `IntoIterator` impl. Use the inherent
+    /// `iter_mut`, `left_iter`, `right_iter`, `into_left_iter` and `into_right_iter` instead.
+    pub enum EitherIterator<T> {
+        Value(T),
+        None,
     }
 
-    #[test]
-    fn should_continue_on_right() {
-        let func = || -> Either<String, i32> {
-            let value = try_either!(Either::right(10));
-            Either::right(value + 10)
-        };
+    impl<T> EitherIterator<T> {
+        fn take(&mut self) -> Option<T> {
+            match core::mem::replace(self, EitherIterator::None) {
+                EitherIterator::Value(value) => Some(value),
+                EitherIterator::None => None,
+            }
+        }
+    }
 
-        let result = func();
+    impl<T> From<Option<T>> for EitherIterator<T> {
+        fn from(value: Option<T>) -> Self {
+            match value {
+                Some(value) => EitherIterator::Value(value),
+                None => EitherIterator::None,
+            }
+        }
+    }
 
-        assert_eq!(result.is_right(), true);
-        assert_eq!(result.unwrap(), 20);
+    impl<T> Iterator for EitherIterator<T> {
+        type Item = T;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.take()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            match self {
+                EitherIterator::Value(_) => (1, Some(1)),
+                EitherIterator::None => (0, Some(0)),
+            }
+        }
     }
 
-    #[test]
-    fn should_map_inner_value() {
-        let value: Either<String, i32> = Either::right(10);
+    impl<T> DoubleEndedIterator for EitherIterator<T> {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            self.take()
+        }
+    }
 
-        let result = value.into_iter().map(|&x| x * 2).collect::<Either<_,_>>();
+    impl<T> ExactSizeIterator for EitherIterator<T> {}
 
-        assert_eq!(result.is_right(), true);
-        assert_eq!(result.unwrap(), 20);
+    impl<T> FusedIterator for EitherIterator<T> {}
+
+    // Iterates over an Either of iterators, keeping the side of every item
+    pub struct FactorIterator<L, R>(Either<L, R>);
+
+    impl<L: Iterator, R: Iterator> Iterator for FactorIterator<L, R> {
+        type Item = Either<L::Item, R::Item>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            match &mut self.0 {
+                Either::Left(iter) => iter.next().map(Either::Left),
+                Either::Right(iter) => iter.next().map(Either::Right),
+            }
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            match &self.0 {
+                Either::Left(iter) => iter.size_hint(),
+                Either::Right(iter) => iter.size_hint(),
+            }
+        }
     }
 
-    #[test]
-    fn should_filter_inner_value() {
-        let value: Either<String, i32> = Either::right(10);
+    impl<L: DoubleEndedIterator, R: DoubleEndedIterator> DoubleEndedIterator for FactorIterator<L, R> {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            match &mut self.0 {
+                Either::Left(iter) => iter.next_back().map(Either::Left),
+                Either::Right(iter) => iter.next_back().map(Either::Right),
+            }
+        }
+    }
 
-        let result = value.into_iter().filter(|&x| *x > 10).collect::<Either<_,_>>();
+    impl<L: ExactSizeIterator, R: ExactSizeIterator> ExactSizeIterator for FactorIterator<L, R> {}
 
-        assert_eq!(result.is_left(), true);
+    impl<L: FusedIterator, R: FusedIterator> FusedIterator for FactorIterator<L, R> {}
+
+    // Either<L, R> and &mut Either<L, R> are iterators themselves when both sides are iterators,
+    // so owning and mutable iteration over one side is done with inherent methods
+    impl<L, R> Either<L, R> {
+        pub fn iter(&self) -> EitherIterator<&R> {
+            self.right_iter()
+        }
+
+        pub fn iter_mut(&mut self) -> EitherIterator<&mut R> {
+            self.as_mut().right().into()
+        }
+
+        pub fn left_iter(&self) -> EitherIterator<&L> {
+            self.as_ref().left().into()
+        }
+
+        pub fn right_iter(&self) -> EitherIterator<&R> {
+            self.as_ref().right().into()
+        }
+
+        pub fn into_left_iter(self) -> EitherIterator<L> {
+            self.left().into()
+        }
+
+        pub fn into_right_iter(self) -> EitherIterator<R> {
+            self.right().into()
+        }
+    }
+
+    impl<L: IntoIterator, R: IntoIterator> Either<L, R> {
+        pub fn factor_into_iter(self) -> FactorIterator<L::IntoIter, R::IntoIter> {
+            FactorIterator(self.map_either(L::into_iter, R::into_iter))
+        }
+    }
+
+    impl<'a, L, R> IntoIterator for &'a Either<L, R> {
+        type Item = &'a R;
+        type IntoIter = EitherIterator<&'a R>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.right_iter()
+        }
+    }
+
+    // Collects Right values, stops on the first Left like Result does
+    impl<L, R, V: FromIterator<R>> FromIterator<Either<L, R>> for Either<L, V> {
+        fn from_iter<T: IntoIterator<Item = Either<L, R>>>(iter: T) -> Self {
+            let mut error = None;
+            let values = iter
+                .into_iter()
+                .map_while(|item| match item {
+                    Either::Left(value) => {
+                        error = Some(value);
+                        None
+                    }
+                    Either::Right(value) => Some(value),
+                })
+                .collect();
+            match error {
+                Some(error) => Either::Left(error),
+                None => Either::Right(values),
+            }
+        }
+    }
+
+    pub trait IteratorExt<L, R>: Iterator<Item = Either<L, R>> + Sized {
+        // Splits items into Left and Right collections
+        fn partition_either<A, B>(self) -> (A, B)
+        where
+            A: Default + Extend<L>,
+            B: Default + Extend<R>,
+        {
+            let mut lefts = A::default();
+            let mut rights = B::default();
+            for item in self {
+                match item {
+                    Either::Left(value) => lefts.extend(Some(value)),
+                    Either::Right(value) => rights.extend(Some(value)),
+                }
+            }
+            (lefts, rights)
+        }
+
+        // Collects all Right values, or all Left values when there is at least one
+        fn collect_all_either<A, B>(self) -> Either<A, B>
+        where
+            A: Default + Extend<L>,
+            B: Default + Extend<R>,
+        {
+            let mut lefts = A::default();
+            let mut rights = B::default();
+            let mut failed = false;
+            for item in self {
+                match item {
+                    Either::Left(value) => {
+                        failed = true;
+                        lefts.extend(Some(value));
+                    }
+                    Either::Right(value) if !failed => rights.extend(Some(value)),
+                    Either::Right(_) => {}
+                }
+            }
+            if failed {
+                Either::Left(lefts)
+            } else {
+                Either::Right(rights)
+            }
+        }
+    }
+
+    impl<L, R, I: Iterator<Item = Either<L, R>>> IteratorExt<L, R> for I {}
+
+    #[macro_export]
+    macro_rules! try_either {
+        ($expr:expr) => {
+            match $expr {
+                Either::Right(value) => value,
+                Either::Left(error) => return Either::Left(error),
+            }
+        };
+    }
+
+    // Converts Left with From like the ? operator
+    #[macro_export]
+    macro_rules! try_either_into {
+        ($expr:expr) => {
+            match $expr {
+                $crate::either::either::Either::Right(value) => value,
+                $crate::either::either::Either::Left(error) => {
+                    return $crate::either::either::Either::Left(::core::convert::From::from(error))
+                }
+            }
+        };
+    }
+
+    // Do-notation desugared into and_then chains, usable as any expression:
+    // either_do! { x <- first(); let y = x * 2; guard y > 0, "negative"; z <- second(y); pure (x + z) }
+    #[macro_export]
+    macro_rules! either_do {
+        (let $pat:pat = $expr:expr; $($rest:tt)+) => {{
+            let $pat = $expr;
+            $crate::either_do!($($rest)+)
+        }};
+        (guard $cond:expr, $error:expr; $($rest:tt)+) => {
+            if $cond {
+                $crate::either_do!($($rest)+)
+            } else {
+                $crate::either::either::Either::Left($error)
+            }
+        };
+        (pure $expr:expr) => {
+            $crate::either::either::Either::Right($expr)
+        };
+        ($pat:tt <- $expr:expr; $($rest:tt)+) => {
+            $crate::either::either::Either::and_then($expr, |$pat| $crate::either_do!($($rest)+))
+        };
+        ($expr:expr; $($rest:tt)+) => {
+            $crate::either::either::Either::and_then($expr, |_| $crate::either_do!($($rest)+))
+        };
+        ($expr:expr) => {
+            $expr
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::either::either::{Either, IteratorExt};
+    use crate::vec::vec;
+    use crate::{either_do, try_either, try_either_into};
+    use std::collections::HashSet;
+    use std::fmt;
+    use std::fmt::Write as _;
+    use std::future::{ready, Future};
+    #[cfg(feature = "std")]
+    use std::io::{self, BufRead, Cursor, Read, Seek, SeekFrom, Write};
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    #[test]
+    fn should_exit_on_left() {
+        let func = || -> Either<i32, String> {
+            try_either!(Either::Left(15));
+            Either::Right(String::from("Hello"))
+        };
+
+        let result = func();
+
+        assert_eq!(result.is_left(), true);
+        assert_eq!(result, Either::Left(15));
+    }
+
+    #[test]
+    fn should_continue_on_right() {
+        let func = || -> Either<String, i32> {
+            let value = try_either!(Either::Right(10));
+            Either::Right(value + 10)
+        };
+
+        let result = func();
+
+        assert_eq!(result.is_right(), true);
+        assert_eq!(result.unwrap(), 20);
+    }
+
+    #[test]
+    fn should_map_inner_value() {
+        let value: Either<String, i32> = Either::Right(10);
+
+        let result = value
+            .into_iter()
+            .map(|&x| Either::<(), i32>::Right(x * 2))
+            .collect::<Either<_, Vec<_>>>();
+
+        assert_eq!(result.is_right(), true);
+        assert_eq!(result.unwrap(), vec![20]);
+    }
+
+    #[test]
+    fn should_filter_inner_value() {
+        let value: Either<String, i32> = Either::Right(10);
+
+        let result = value
+            .into_iter()
+            .map(|&x| {
+                if x > 10 {
+                    Either::Right(x)
+                } else {
+                    Either::Left(())
+                }
+            })
+            .collect::<Either<_, Vec<_>>>();
+
+        assert_eq!(result.is_left(), true);
+    }
+
+    #[test]
+    fn should_take_first_inner_value() {
+        let value: Either<String, i32> = Either::Right(10);
+
+        let result = Either::ok_or(value.into_iter().map(|&x| x * 2).next(), ());
+
+        assert_eq!(result.is_right(), true);
+        assert_eq!(result.unwrap(), 20);
+    }
+
+    #[test]
+    fn should_return_left_when_inner_value_not_found() {
+        let value: Either<String, i32> = Either::Right(10);
+
+        let result = Either::ok_or(value.into_iter().find(|&x| *x > 10), ());
+
+        assert_eq!(result.is_left(), true);
+    }
+
+    #[test]
+    fn should_return_option_of_side() {
+        let left: Either<String, i32> = Either::Left(String::from("Error"));
+        let right: Either<String, i32> = Either::Right(10);
+
+        assert_eq!(left.left(), Some(String::from("Error")));
+        assert_eq!(right.right(), Some(10));
+    }
+
+    #[test]
+    fn should_map_right_value() {
+        let value: Either<String, i32> = Either::Right(10);
+
+        let result = value.map(|x| x * 2);
+
+        assert_eq!(result.unwrap(), 20);
+    }
+
+    #[test]
+    fn should_not_map_left_value() {
+        let value: Either<String, i32> = Either::Left(String::from("Error"));
+
+        let result = value.map(|x| x * 2);
+
+        assert_eq!(result.unwrap_left(), "Error");
+    }
+
+    #[test]
+    fn should_map_left_value() {
+        let value: Either<i32, String> = Either::Left(10);
+
+        let result = value.map_left(|x| x.to_string());
+
+        assert_eq!(result.unwrap_left(), "10");
+    }
+
+    #[test]
+    fn should_map_either_side() {
+        let left: Either<i32, i32> = Either::Left(10);
+        let right: Either<i32, i32> = Either::Right(10);
+
+        let left = left.map_either(|x| x + 1, |x| x * 2);
+        let right = right.map_either(|x| x + 1, |x| x * 2);
+
+        assert_eq!(left.unwrap_left(), 11);
+        assert_eq!(right.unwrap(), 20);
+    }
+
+    #[test]
+    fn should_chain_right_values() {
+        let half = |x: i32| -> Either<String, i32> {
+            if x % 2 == 0 {
+                Either::Right(x / 2)
+            } else {
+                Either::Left(format!("{} is odd", x))
+            }
+        };
+
+        let result = Either::Right(12)
+            .and_then(half)
+            .and_then(half)
+            .and_then(half);
+
+        assert_eq!(result.unwrap_left(), "3 is odd");
+    }
+
+    #[test]
+    fn should_recover_from_left() {
+        let value: Either<String, i32> = Either::Left(String::from("10"));
+
+        let result: Either<(), i32> = value.or_else(|x| Either::Right(x.parse().unwrap()));
+
+        assert_eq!(result.unwrap(), 10);
+    }
+
+    #[test]
+    fn should_reduce_both_sides() {
+        let left: Either<i32, String> = Either::Left(10);
+        let right: Either<i32, String> = Either::Right(String::from("Hello"));
+
+        assert_eq!(left.either(|x| x.to_string(), |x| x), "10");
+        assert_eq!(right.either(|x| x.to_string(), |x| x), "Hello");
+    }
+
+    #[test]
+    fn should_flip_sides() {
+        let value: Either<String, i32> = Either::Right(10);
+
+        let result = value.flip();
+
+        assert_eq!(result.unwrap_left(), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected right value")]
+    fn should_panic_with_message_on_left() {
+        let value: Either<String, i32> = Either::Left(String::from("Error"));
+
+        value.expect("Expected right value");
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected left value")]
+    fn should_panic_with_message_on_right() {
+        let value: Either<String, i32> = Either::Right(10);
+
+        value.expect_left("Expected left value");
+    }
+
+    #[test]
+    fn should_return_default_on_left() {
+        let value: Either<String, i32> = Either::Left(String::from("Error"));
+        let other: Either<String, i32> = Either::Left(String::from("Error"));
+        let another: Either<String, i32> = Either::Left(String::from("Error"));
+
+        assert_eq!(value.unwrap_or(5), 5);
+        assert_eq!(other.unwrap_or_else(|x| x.len() as i32), 5);
+        assert_eq!(another.unwrap_or_default(), 0);
+    }
+
+    #[test]
+    fn should_ignore_default_on_right() {
+        let value: Either<String, i32> = Either::Right(10);
+
+        assert_eq!(value.unwrap_or(5), 10);
+    }
+
+    #[test]
+    fn should_borrow_without_moving() {
+        let value: Either<String, String> = Either::Right(String::from("Hello"));
+
+        let result = value.as_ref().map(|x| x.len());
+
+        assert_eq!(result.unwrap(), 5);
+        assert_eq!(value.is_right(), true);
+    }
+
+    #[test]
+    fn should_mutate_through_borrow() {
+        let mut value: Either<String, i32> = Either::Right(10);
+
+        if let Either::Right(x) = value.as_mut() {
+            *x += 1;
+        }
+
+        assert_eq!(value.unwrap(), 11);
+    }
+
+    #[test]
+    fn should_deref_both_sides() {
+        let value: Either<String, Vec<i32>> = Either::Left(String::from("Hello"));
+
+        let result: Either<&str, &[i32]> = value.as_deref();
+
+        assert_eq!(result.unwrap_left(), "Hello");
+    }
+
+    #[test]
+    fn should_deref_mut_both_sides() {
+        let mut value: Either<String, Vec<i32>> = Either::Right(vec![3, 1, 2]);
+
+        value.as_deref_mut().map(|x| x.sort());
+
+        assert_eq!(value.unwrap(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn should_project_pinned_value() {
+        let mut value = pin!(Either::<String, i32>::Right(10));
+
+        if let Either::Right(x) = value.as_mut().as_pin_mut() {
+            *x.get_mut() += 1;
+        }
+
+        assert_eq!(*value.as_ref().as_pin_ref().unwrap(), 11);
+    }
+
+    #[test]
+    fn should_clone_borrowed_value() {
+        let value: Either<String, i32> = Either::Left(String::from("Hello"));
+
+        let result = value.as_ref().cloned();
+
+        assert_eq!(result.unwrap_left(), "Hello");
+        assert_eq!(value.unwrap_left(), "Hello");
+    }
+
+    #[test]
+    fn should_copy_borrowed_value() {
+        let mut value: Either<char, i32> = Either::Right(10);
+
+        let first = value.as_ref().copied();
+        let second = value.as_mut().copied();
+
+        assert_eq!(first.unwrap(), 10);
+        assert_eq!(second.unwrap(), 10);
+    }
+
+    #[test]
+    fn should_convert_from_result() {
+        let ok: Result<i32, String> = Ok(10);
+        let err: Result<i32, String> = Err(String::from("Error"));
+
+        assert_eq!(Either::from(ok).unwrap(), 10);
+        assert_eq!(Either::from(err).unwrap_left(), "Error");
+    }
+
+    #[test]
+    fn should_convert_into_result() {
+        let right: Either<String, i32> = Either::Right(10);
+        let left: Either<String, i32> = Either::Left(String::from("Error"));
+
+        let right: Result<i32, String> = right.into();
+        let left: Result<i32, String> = left.into();
+
+        assert_eq!(right, Ok(10));
+        assert_eq!(left, Err(String::from("Error")));
+    }
+
+    #[test]
+    fn should_return_option_like_result() {
+        let right: Either<String, i32> = Either::Right(10);
+        let left: Either<String, i32> = Either::Left(String::from("Error"));
+
+        assert_eq!(right.ok(), Some(10));
+        assert_eq!(left.err(), Some(String::from("Error")));
+    }
+
+    #[test]
+    fn should_create_from_option() {
+        let some: Either<String, i32> = Either::ok_or(Some(10), String::from("Missing"));
+        let none: Either<String, i32> = Either::ok_or(None, String::from("Missing"));
+        let lazy: Either<String, i32> = Either::ok_or_else(None, || String::from("Missing"));
+
+        assert_eq!(some.unwrap(), 10);
+        assert_eq!(none.unwrap_left(), "Missing");
+        assert_eq!(lazy.unwrap_left(), "Missing");
+    }
+
+    #[test]
+    fn should_transpose_option() {
+        let some: Either<String, Option<i32>> = Either::Right(Some(10));
+        let none: Either<String, Option<i32>> = Either::Right(None);
+        let left: Either<String, Option<i32>> = Either::Left(String::from("Error"));
+
+        assert_eq!(some.transpose().unwrap().unwrap(), 10);
+        assert_eq!(none.transpose().is_none(), true);
+        assert_eq!(left.transpose().unwrap().unwrap_left(), "Error");
+    }
+
+    #[test]
+    fn should_convert_left_on_exit() {
+        let func = || -> Either<String, i32> {
+            let value = try_either_into!(Either::<&str, i32>::Left("Error"));
+            Either::Right(value)
+        };
+
+        let result = func();
+
+        assert_eq!(result.unwrap_left(), "Error");
+    }
+
+    #[test]
+    fn should_convert_left_without_either_in_scope() {
+        mod caller {
+            // Shadows prelude From, expansion must not pick it up
+            #[allow(dead_code)]
+            trait From {}
+
+            pub fn parse(
+                value: crate::either::either::Either<u8, i32>,
+            ) -> crate::either::either::Either<i64, i32> {
+                let value = crate::try_either_into!(value);
+                crate::either::either::Either::Right(value + 1)
+            }
+        }
+
+        assert_eq!(
+            caller::parse(crate::either::either::Either::Left(7)).unwrap_left(),
+            7
+        );
+        assert_eq!(
+            caller::parse(crate::either::either::Either::Right(7)).unwrap(),
+            8
+        );
+    }
+
+    #[test]
+    fn should_compare_values() {
+        let first: Either<String, i32> = Either::Right(10);
+        let second = first.clone();
+
+        assert_eq!(first, second);
+        assert_ne!(first, Either::Right(11));
+        assert_ne!(first, Either::Left(String::from("10")));
+    }
+
+    #[test]
+    fn should_order_left_before_right() {
+        let mut values: Vec<Either<i32, i32>> =
+            vec![Either::Right(1), Either::Left(2), Either::Left(1)];
+
+        values.sort();
+
+        assert_eq!(values, [Either::Left(1), Either::Left(2), Either::Right(1)]);
+    }
+
+    #[test]
+    fn should_hash_values() {
+        let mut set: HashSet<Either<i32, i32>> = HashSet::new();
+
+        set.insert(Either::Left(1));
+        set.insert(Either::Right(1));
+        set.insert(Either::Left(1));
+
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn should_copy_value() {
+        let first: Either<i32, char> = Either::Right('a');
+        let second = first;
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn should_format_values() {
+        let left: Either<i32, String> = Either::Left(10);
+        let right: Either<i32, String> = Either::Right(String::from("Hello"));
+
+        assert_eq!(left.to_string(), "10");
+        assert_eq!(right.to_string(), "Hello");
+        assert_eq!(format!("{:?}", right), "Right(\"Hello\")");
+    }
+
+    #[derive(Debug)]
+    struct Wrapped(fmt::Error);
+
+    impl fmt::Display for Wrapped {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "Wrapped")
+        }
+    }
+
+    impl std::error::Error for Wrapped {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn should_act_as_error() {
+        let value: Either<Wrapped, fmt::Error> = Either::Left(Wrapped(fmt::Error));
+
+        let error: Box<dyn std::error::Error> = Box::new(value);
+
+        assert_eq!(error.to_string(), "Wrapped");
+        assert_eq!(error.source().is_some(), true);
+    }
+
+    fn numbers(
+        reversed: bool,
+    ) -> Either<std::ops::Range<i32>, std::iter::Rev<std::ops::Range<i32>>> {
+        if reversed {
+            Either::Right((1..4).rev())
+        } else {
+            Either::Left(1..4)
+        }
+    }
+
+    #[test]
+    fn should_iterate_active_side() {
+        let forward: Vec<i32> = numbers(false).collect();
+        let backward: Vec<i32> = numbers(true).collect();
+
+        assert_eq!(forward, [1, 2, 3]);
+        assert_eq!(backward, [3, 2, 1]);
+    }
+
+    #[test]
+    fn should_iterate_from_back() {
+        let mut uat = numbers(false);
+
+        assert_eq!(uat.len(), 3);
+        assert_eq!(uat.next_back(), Some(3));
+        assert_eq!(uat.next(), Some(1));
+        assert_eq!(uat.len(), 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn should_read_from_active_side() {
+        let mut uat: Either<io::Empty, &[u8]> = Either::Right(b"Hello\nWorld");
+        let mut line = String::new();
+        let mut rest = String::new();
+
+        uat.read_line(&mut line).unwrap();
+        uat.read_to_string(&mut rest).unwrap();
+
+        assert_eq!(line, "Hello\n");
+        assert_eq!(rest, "World");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn should_write_to_active_side() {
+        let mut uat: Either<Vec<u8>, io::Sink> = Either::Left(Vec::new());
+
+        uat.write_all(b"Hello").unwrap();
+        uat.flush().unwrap();
+
+        assert_eq!(uat.unwrap_left(), b"Hello");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn should_seek_active_side() {
+        let mut uat: Either<Cursor<Vec<u8>>, Cursor<&[u8]>> = Either::Right(Cursor::new(b"Hello"));
+        let mut buf = [0; 2];
+
+        uat.seek(SeekFrom::End(-2)).unwrap();
+        uat.read_exact(&mut buf).unwrap();
+
+        assert_eq!(&buf, b"lo");
+    }
+
+    #[test]
+    fn should_format_into_active_side() {
+        let mut uat: Either<String, String> = Either::Right(String::new());
+
+        write!(uat, "{}-{}", 1, 2).unwrap();
+
+        assert_eq!(uat.unwrap(), "1-2");
+    }
+
+    #[test]
+    fn should_deref_to_common_target() {
+        let left: Either<String, Box<str>> = Either::Left(String::from("Hello"));
+        let right: Either<String, Box<str>> = Either::Right(Box::from("World"));
+
+        assert_eq!(left.len(), 5);
+        assert_eq!(&*right, "World");
+    }
+
+    #[test]
+    fn should_poll_active_future() {
+        let mut uat: Either<std::future::Ready<i32>, std::future::Pending<i32>> =
+            Either::Left(ready(10));
+        let mut cx = Context::from_waker(Waker::noop());
+
+        let result = std::pin::Pin::new(&mut uat).poll(&mut cx);
+
+        assert_eq!(result, Poll::Ready(10));
+    }
+
+    #[test]
+    fn should_iterate_over_right_value() {
+        let value: Either<String, i32> = Either::Right(10);
+        let mut iter = value.iter();
+
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next_back(), Some(&10));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn should_mutate_right_value_in_loop() {
+        let mut value: Either<String, i32> = Either::Right(10);
+
+        for x in value.iter_mut() {
+            *x += 1;
+        }
+
+        assert_eq!(value, Either::Right(11));
+    }
+
+    #[test]
+    fn should_iterate_over_selected_side() {
+        let value: Either<String, i32> = Either::Left(String::from("Error"));
+
+        assert_eq!(value.left_iter().count(), 1);
+        assert_eq!(value.right_iter().count(), 0);
+    }
+
+    #[test]
+    fn should_move_values_out() {
+        let left: Either<String, String> = Either::Left(String::from("Error"));
+        let right: Either<String, String> = Either::Right(String::from("Hello"));
+
+        let left: Vec<String> = left.into_left_iter().collect();
+        let right: Vec<String> = right.into_right_iter().collect();
+
+        assert_eq!(left, ["Error"]);
+        assert_eq!(right, ["Hello"]);
+    }
+
+    #[test]
+    fn should_factor_items_of_iterators() {
+        let value: Either<Vec<i32>, &str> = Either::Left(vec![1, 2, 3]);
+
+        let result: Vec<Either<i32, char>> =
+            value.map(|x| x.chars()).factor_into_iter().rev().collect();
+
+        assert_eq!(result, [Either::Left(3), Either::Left(2), Either::Left(1)]);
+    }
+
+    #[test]
+    fn should_factor_items_of_right_iterator() {
+        let value: Either<Vec<i32>, &str> = Either::Right("ab");
+
+        let mut iter = value.map(|x| x.chars()).factor_into_iter();
+
+        assert_eq!(iter.next(), Some(Either::Right('a')));
+        assert_eq!(iter.next(), Some(Either::Right('b')));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn should_collect_all_right_values() {
+        let values: Vec<Either<String, i32>> = vec![Either::Right(1), Either::Right(2)];
+
+        let result: Either<String, Vec<i32>> = values.into_iter().collect();
+
+        assert_eq!(result, Either::Right(vec![1, 2]));
+    }
+
+    #[test]
+    fn should_stop_collecting_on_first_left() {
+        let mut consumed = 0;
+        let values = vec![
+            Either::Right(1),
+            Either::Left("first"),
+            Either::Left("second"),
+            Either::Right(2),
+        ];
+
+        let result: Either<&str, Vec<i32>> =
+            values.into_iter().inspect(|_| consumed += 1).collect();
+
+        assert_eq!(result, Either::Left("first"));
+        assert_eq!(consumed, 2);
+    }
+
+    #[test]
+    fn should_partition_by_side() {
+        let values = vec![Either::Left("a"), Either::Right(1), Either::Left("b")];
+
+        let (lefts, rights): (Vec<&str>, Vec<i32>) = values.into_iter().partition_either();
+
+        assert_eq!(lefts, ["a", "b"]);
+        assert_eq!(rights, [1]);
+    }
+
+    #[test]
+    fn should_collect_all_lefts_when_any_left() {
+        let values = vec![
+            Either::Right(1),
+            Either::Left("a"),
+            Either::Right(2),
+            Either::Left("b"),
+        ];
+
+        let result: Either<Vec<&str>, Vec<i32>> = values.into_iter().collect_all_either();
+
+        assert_eq!(result, Either::Left(vec!["a", "b"]));
+    }
+
+    #[test]
+    fn should_collect_all_rights_into_crate_vec() {
+        let values: Vec<Either<&str, i32>> = vec![Either::Right(1), Either::Right(2)];
+
+        let result: Either<vec::Vec<&str>, vec::Vec<i32>> = values.into_iter().collect_all_either();
+
+        let rights = result.unwrap();
+        assert_eq!(rights.len(), 2);
+        assert_eq!(rights[1], 2);
+    }
+
+    fn parse(value: &str) -> Either<String, i32> {
+        match value.parse() {
+            Ok(value) => Either::Right(value),
+            Err(_) => Either::Left(format!("{} is not a number", value)),
+        }
+    }
+
+    #[test]
+    fn should_chain_bindings() {
+        let result = either_do! {
+            x <- parse("1");
+            y <- parse("2");
+            pure (x + y)
+        };
+
+        assert_eq!(result, Either::Right(3));
+    }
+
+    #[test]
+    fn should_stop_on_first_left() {
+        let mut evaluated = false;
+
+        let result = either_do! {
+            x <- parse("a");
+            y <- {
+                evaluated = true;
+                parse("2")
+            };
+            pure (x + y)
+        };
+
+        assert_eq!(result, Either::Left(String::from("a is not a number")));
+        assert_eq!(evaluated, false);
+    }
+
+    #[test]
+    fn should_support_let_bindings() {
+        let result = either_do! {
+            x <- parse("4");
+            let (double, triple) = (x * 2, x * 3);
+            pure (double + triple)
+        };
+
+        assert_eq!(result, Either::Right(20));
+    }
+
+    #[test]
+    fn should_return_left_from_failed_guard() {
+        let check = |value: &str| {
+            either_do! {
+                x <- parse(value);
+                guard x > 0, format!("{} is not positive", x);
+                pure x
+            }
+        };
+
+        assert_eq!(check("5"), Either::Right(5));
+        assert_eq!(
+            check("-5"),
+            Either::Left(String::from("-5 is not positive"))
+        );
+    }
+
+    #[test]
+    fn should_support_nesting() {
+        let result = either_do! {
+            x <- parse("1");
+            y <- either_do! {
+                a <- parse("2");
+                b <- parse("3");
+                pure (a * b)
+            };
+            pure (x + y)
+        };
+
+        assert_eq!(result, Either::Right(7));
+    }
+
+    #[test]
+    fn should_end_with_either_expression() {
+        let result = either_do! {
+            x <- parse("1");
+            parse("2");
+            (a, b) <- Either::Right((x, 10));
+            parse(&(a + b).to_string())
+        };
+
+        assert_eq!(result, Either::Right(11));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_round_trip_through_json() {
+        let values: Vec<Either<String, i32>> =
+            vec![Either::Left(String::from("Error")), Either::Right(10)];
+
+        let json = serde_json::to_string(&values).unwrap();
+        let result: Vec<Either<String, i32>> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(json, "[{\"Left\":\"Error\"},{\"Right\":10}]");
+        assert_eq!(result, values);
     }
 }
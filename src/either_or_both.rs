@@ -0,0 +1,259 @@
+pub mod either_or_both {
+    use crate::either::either::Either;
+    use core::cmp::Ordering;
+    use core::iter::Peekable;
+
+    // Value present on the left side, the right side or on both of them, like a key in merge join
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum EitherOrBoth<L, R> {
+        Left(L),
+        Right(R),
+        Both(L, R),
+    }
+
+    impl<L, R> EitherOrBoth<L, R> {
+        pub fn is_left(&self) -> bool {
+            matches!(self, EitherOrBoth::Left(_))
+        }
+
+        pub fn is_right(&self) -> bool {
+            matches!(self, EitherOrBoth::Right(_))
+        }
+
+        pub fn is_both(&self) -> bool {
+            matches!(self, EitherOrBoth::Both(_, _))
+        }
+
+        pub fn has_left(&self) -> bool {
+            !self.is_right()
+        }
+
+        pub fn has_right(&self) -> bool {
+            !self.is_left()
+        }
+
+        pub fn left(self) -> Option<L> {
+            match self {
+                EitherOrBoth::Left(left) | EitherOrBoth::Both(left, _) => Some(left),
+                EitherOrBoth::Right(_) => None,
+            }
+        }
+
+        pub fn right(self) -> Option<R> {
+            match self {
+                EitherOrBoth::Right(right) | EitherOrBoth::Both(_, right) => Some(right),
+                EitherOrBoth::Left(_) => None,
+            }
+        }
+
+        pub fn both(self) -> Option<(L, R)> {
+            match self {
+                EitherOrBoth::Both(left, right) => Some((left, right)),
+                _ => None,
+            }
+        }
+
+        pub fn map_any<F, G, M, S>(self, f: F, g: G) -> EitherOrBoth<M, S>
+        where
+            F: FnOnce(L) -> M,
+            G: FnOnce(R) -> S,
+        {
+            match self {
+                EitherOrBoth::Left(left) => EitherOrBoth::Left(f(left)),
+                EitherOrBoth::Right(right) => EitherOrBoth::Right(g(right)),
+                EitherOrBoth::Both(left, right) => EitherOrBoth::Both(f(left), g(right)),
+            }
+        }
+
+        pub fn flip(self) -> EitherOrBoth<R, L> {
+            match self {
+                EitherOrBoth::Left(left) => EitherOrBoth::Right(left),
+                EitherOrBoth::Right(right) => EitherOrBoth::Left(right),
+                EitherOrBoth::Both(left, right) => EitherOrBoth::Both(right, left),
+            }
+        }
+    }
+
+    impl<T> EitherOrBoth<T, T> {
+        // Returns the only value, or combines both of them with f
+        pub fn reduce<F>(self, f: F) -> T
+        where
+            F: FnOnce(T, T) -> T,
+        {
+            match self {
+                EitherOrBoth::Left(value) | EitherOrBoth::Right(value) => value,
+                EitherOrBoth::Both(left, right) => f(left, right),
+            }
+        }
+    }
+
+    impl<L, R> From<Either<L, R>> for EitherOrBoth<L, R> {
+        fn from(value: Either<L, R>) -> Self {
+            match value {
+                Either::Left(left) => EitherOrBoth::Left(left),
+                Either::Right(right) => EitherOrBoth::Right(right),
+            }
+        }
+    }
+
+    // Both has no Either counterpart, its values are returned as error
+    impl<L, R> TryFrom<EitherOrBoth<L, R>> for Either<L, R> {
+        type Error = (L, R);
+
+        fn try_from(value: EitherOrBoth<L, R>) -> Result<Self, Self::Error> {
+            match value {
+                EitherOrBoth::Left(left) => Ok(Either::Left(left)),
+                EitherOrBoth::Right(right) => Ok(Either::Right(right)),
+                EitherOrBoth::Both(left, right) => Err((left, right)),
+            }
+        }
+    }
+
+    pub struct MergeJoinBy<I: Iterator, J: Iterator, F> {
+        left: Peekable<I>,
+        right: Peekable<J>,
+        cmp: F,
+    }
+
+    impl<I, J, F> Iterator for MergeJoinBy<I, J, F>
+    where
+        I: Iterator,
+        J: Iterator,
+        F: FnMut(&I::Item, &J::Item) -> Ordering,
+    {
+        type Item = EitherOrBoth<I::Item, J::Item>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let ordering = match (self.left.peek(), self.right.peek()) {
+                (None, None) => return None,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(left), Some(right)) => (self.cmp)(left, right),
+            };
+            match ordering {
+                Ordering::Less => self.left.next().map(EitherOrBoth::Left),
+                Ordering::Greater => self.right.next().map(EitherOrBoth::Right),
+                Ordering::Equal => match (self.left.next(), self.right.next()) {
+                    (Some(left), Some(right)) => Some(EitherOrBoth::Both(left, right)),
+                    _ => None,
+                },
+            }
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let (left_lower, left_upper) = self.left.size_hint();
+            let (right_lower, right_upper) = self.right.size_hint();
+            let upper = match (left_upper, right_upper) {
+                (Some(left), Some(right)) => left.checked_add(right),
+                _ => None,
+            };
+            (left_lower.max(right_lower), upper)
+        }
+    }
+
+    pub trait MergeJoinExt: Iterator + Sized {
+        // Merges two iterators sorted by cmp, items comparing equal are paired in Both
+        fn merge_join_by<J, F>(self, other: J, cmp: F) -> MergeJoinBy<Self, J::IntoIter, F>
+        where
+            J: IntoIterator,
+            F: FnMut(&Self::Item, &J::Item) -> Ordering,
+        {
+            MergeJoinBy {
+                left: self.peekable(),
+                right: other.into_iter().peekable(),
+                cmp,
+            }
+        }
+    }
+
+    impl<I: Iterator> MergeJoinExt for I {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::either::either::Either;
+    use crate::either_or_both::either_or_both::{EitherOrBoth, MergeJoinExt};
+
+    #[test]
+    fn should_return_sides() {
+        let both: EitherOrBoth<i32, &str> = EitherOrBoth::Both(1, "a");
+        let left: EitherOrBoth<i32, &str> = EitherOrBoth::Left(1);
+
+        assert_eq!(both.left(), Some(1));
+        assert_eq!(both.right(), Some("a"));
+        assert_eq!(both.both(), Some((1, "a")));
+        assert_eq!(left.right(), None);
+        assert_eq!(left.both(), None);
+        assert_eq!(left.has_left(), true);
+        assert_eq!(both.has_right(), true);
+    }
+
+    #[test]
+    fn should_map_present_values() {
+        let both: EitherOrBoth<i32, &str> = EitherOrBoth::Both(1, "a");
+
+        let result = both.map_any(|x| x + 1, |x| x.len());
+
+        assert_eq!(result, EitherOrBoth::Both(2, 1));
+    }
+
+    #[test]
+    fn should_reduce_values() {
+        let both: EitherOrBoth<i32, i32> = EitherOrBoth::Both(1, 2);
+        let right: EitherOrBoth<i32, i32> = EitherOrBoth::Right(2);
+
+        assert_eq!(both.reduce(|a, b| a + b), 3);
+        assert_eq!(right.reduce(|a, b| a + b), 2);
+    }
+
+    #[test]
+    fn should_convert_from_either() {
+        let value: Either<i32, &str> = Either::Right("a");
+
+        let result = EitherOrBoth::from(value);
+
+        assert_eq!(result, EitherOrBoth::Right("a"));
+    }
+
+    #[test]
+    fn should_convert_into_either() {
+        let left: EitherOrBoth<i32, &str> = EitherOrBoth::Left(1);
+        let both: EitherOrBoth<i32, &str> = EitherOrBoth::Both(1, "a");
+
+        assert_eq!(Either::try_from(left), Ok(Either::Left(1)));
+        assert_eq!(Either::try_from(both), Err((1, "a")));
+    }
+
+    #[test]
+    fn should_merge_join_sorted_iterators() {
+        let left = [1, 3, 5, 6];
+        let right = ["3", "4", "6", "7"];
+
+        let result: Vec<EitherOrBoth<i32, &str>> = left
+            .into_iter()
+            .merge_join_by(right, |l, r| l.cmp(&r.parse().unwrap()))
+            .collect();
+
+        assert_eq!(
+            result,
+            [
+                EitherOrBoth::Left(1),
+                EitherOrBoth::Both(3, "3"),
+                EitherOrBoth::Right("4"),
+                EitherOrBoth::Left(5),
+                EitherOrBoth::Both(6, "6"),
+                EitherOrBoth::Right("7"),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_merge_join_with_empty_side() {
+        let result: Vec<EitherOrBoth<i32, i32>> = [1, 2]
+            .into_iter()
+            .merge_join_by(Vec::new(), |l, r| l.cmp(r))
+            .collect();
+
+        assert_eq!(result, [EitherOrBoth::Left(1), EitherOrBoth::Left(2)]);
+    }
+}
@@ -0,0 +1,475 @@
+pub mod gc {
+    // Reference counting with synchronous cycle collection (Bacon & Rajan, 2001).
+    // Every decrement that does not free a value records it as a possible cycle root,
+    // collect_cycles() then runs trial deletion over recorded roots and frees unreachable cycles.
+    // It is a separate pointer instead of a mode of rc::Rc: trial deletion has to trace children,
+    // which would put a Trace bound and color fields on every Rc, including Rc<str> and Rc<dyn Any>.
+    // Cycles of rc::Rc still leak and are broken with rc::Weak, values that may form cycles use Gc.
+    use std::alloc::{alloc, dealloc, handle_alloc_error, Layout};
+    use std::cell::{Cell, RefCell};
+    use std::mem;
+    use std::ops::Deref;
+    use std::ptr;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Color {
+        // In use or free
+        Black,
+        // Possible member of cycle
+        Gray,
+        // Member of garbage cycle
+        White,
+        // Possible root of cycle
+        Purple,
+    }
+
+    struct GcBox<T: ?Sized> {
+        strong: Cell<usize>,
+        color: Cell<Color>,
+        buffered: Cell<bool>,
+        value: T,
+    }
+
+    type Node = *mut GcBox<dyn Trace>;
+
+    thread_local! {
+        static ROOTS: RefCell<Vec<Node>> = const { RefCell::new(Vec::new()) };
+    }
+
+    pub trait Trace {
+        // Should visit every Gc directly owned by self
+        fn trace(&self, tracer: &mut Tracer);
+    }
+
+    pub struct Tracer<'a>(&'a mut dyn FnMut(Node));
+
+    impl Tracer<'_> {
+        pub fn visit<T: Trace + 'static>(&mut self, gc: &Gc<T>) {
+            (self.0)(gc.0 as Node)
+        }
+    }
+
+    pub struct Gc<T: Trace + 'static>(*mut GcBox<T>);
+
+    impl<T: Trace + 'static> Gc<T> {
+        pub fn new(value: T) -> Self {
+            unsafe {
+                let layout = Layout::new::<GcBox<T>>();
+                let ptr = alloc(layout) as *mut GcBox<T>;
+                if ptr.is_null() {
+                    handle_alloc_error(layout);
+                }
+                ptr.write(GcBox {
+                    strong: Cell::new(1),
+                    color: Cell::new(Color::Black),
+                    buffered: Cell::new(false),
+                    value,
+                });
+                Gc(ptr)
+            }
+        }
+
+        pub fn strong_count(this: &Self) -> usize {
+            unsafe { (*this.0).strong.get() }
+        }
+    }
+
+    impl<T: Trace + 'static> Deref for Gc<T> {
+        type Target = T;
+
+        fn deref(&self) -> &Self::Target {
+            unsafe { &(*self.0).value }
+        }
+    }
+
+    impl<T: Trace + 'static> Clone for Gc<T> {
+        fn clone(&self) -> Self {
+            unsafe {
+                (*self.0).strong.set((*self.0).strong.get() + 1);
+                (*self.0).color.set(Color::Black);
+                Gc(self.0)
+            }
+        }
+    }
+
+    impl<T: Trace + 'static> Drop for Gc<T> {
+        fn drop(&mut self) {
+            unsafe { decrement(self.0 as Node) }
+        }
+    }
+
+    impl<T: Trace + 'static> Trace for Gc<T> {
+        fn trace(&self, tracer: &mut Tracer) {
+            tracer.visit(self)
+        }
+    }
+
+    // Frees every unreachable cycle among values recorded since the previous collection
+    pub fn collect_cycles() {
+        let roots = ROOTS.with(|roots| mem::take(&mut *roots.borrow_mut()));
+        unsafe {
+            let candidates = mark_roots(roots);
+            for &node in &candidates {
+                scan(node);
+            }
+            let mut garbage = Vec::new();
+            for node in candidates {
+                (*node).buffered.set(false);
+                collect_white(node, &mut garbage);
+            }
+            // White marks values being freed so that handles between them are not decremented
+            for &node in &garbage {
+                (*node).color.set(Color::White);
+            }
+            // Trial deletion already took away references held by garbage, the ones to live values
+            // are given back so that dropping the garbage releases each of them exactly once
+            for &node in &garbage {
+                for child in children(node) {
+                    if (*child).color.get() != Color::White {
+                        (*child).strong.set((*child).strong.get() + 1);
+                    }
+                }
+            }
+            for &node in &garbage {
+                ptr::drop_in_place(ptr::addr_of_mut!((*node).value));
+            }
+            for node in garbage {
+                free(node);
+            }
+        }
+    }
+
+    unsafe fn children(node: Node) -> Vec<Node> {
+        let mut result = Vec::new();
+        (*node)
+            .value
+            .trace(&mut Tracer(&mut |child| result.push(child)));
+        result
+    }
+
+    unsafe fn free(node: Node) {
+        dealloc(node as *mut u8, Layout::for_value(&*node));
+    }
+
+    unsafe fn decrement(node: Node) {
+        // Only header fields are borrowed, value may be in the middle of being dropped
+        if (*node).color.get() == Color::White {
+            return;
+        }
+        (*node).strong.set((*node).strong.get() - 1);
+        if (*node).strong.get() == 0 {
+            release(node);
+        } else {
+            possible_root(node);
+        }
+    }
+
+    unsafe fn release(node: Node) {
+        (*node).color.set(Color::Black);
+        ptr::drop_in_place(ptr::addr_of_mut!((*node).value));
+        // Buffered values are freed by the next collection
+        if !(*node).buffered.get() {
+            free(node);
+        }
+    }
+
+    unsafe fn possible_root(node: Node) {
+        if (*node).color.get() != Color::Purple {
+            (*node).color.set(Color::Purple);
+            if !(*node).buffered.get() {
+                (*node).buffered.set(true);
+                // Roots are gone only when the thread is exiting, value leaks then
+                let _ = ROOTS.try_with(|roots| roots.borrow_mut().push(node));
+            }
+        }
+    }
+
+    unsafe fn mark_roots(roots: Vec<Node>) -> Vec<Node> {
+        let mut candidates = Vec::new();
+        for node in roots {
+            if (*node).color.get() == Color::Purple {
+                mark_gray(node);
+                candidates.push(node);
+            } else {
+                (*node).buffered.set(false);
+                if (*node).color.get() == Color::Black && (*node).strong.get() == 0 {
+                    free(node);
+                }
+            }
+        }
+        candidates
+    }
+
+    unsafe fn mark_gray(node: Node) {
+        if (*node).color.get() != Color::Gray {
+            (*node).color.set(Color::Gray);
+            for child in children(node) {
+                (*child).strong.set((*child).strong.get() - 1);
+                mark_gray(child);
+            }
+        }
+    }
+
+    unsafe fn scan(node: Node) {
+        if (*node).color.get() == Color::Gray {
+            if (*node).strong.get() > 0 {
+                scan_black(node);
+            } else {
+                (*node).color.set(Color::White);
+                for child in children(node) {
+                    scan(child);
+                }
+            }
+        }
+    }
+
+    unsafe fn scan_black(node: Node) {
+        (*node).color.set(Color::Black);
+        for child in children(node) {
+            (*child).strong.set((*child).strong.get() + 1);
+            if (*child).color.get() != Color::Black {
+                scan_black(child);
+            }
+        }
+    }
+
+    unsafe fn collect_white(node: Node, garbage: &mut Vec<Node>) {
+        if (*node).color.get() == Color::White && !(*node).buffered.get() {
+            (*node).color.set(Color::Black);
+            for child in children(node) {
+                collect_white(child, garbage);
+            }
+            garbage.push(node);
+        }
+    }
+
+    macro_rules! empty_trace {
+        ($($t:ty),*) => {
+            $(
+                impl Trace for $t {
+                    fn trace(&self, _tracer: &mut Tracer) {}
+                }
+            )*
+        };
+    }
+
+    empty_trace!(
+        (),
+        bool,
+        char,
+        i8,
+        i16,
+        i32,
+        i64,
+        i128,
+        isize,
+        u8,
+        u16,
+        u32,
+        u64,
+        u128,
+        usize,
+        f32,
+        f64,
+        String,
+        &'static str
+    );
+
+    impl<T: Trace> Trace for Option<T> {
+        fn trace(&self, tracer: &mut Tracer) {
+            if let Some(value) = self {
+                value.trace(tracer);
+            }
+        }
+    }
+
+    impl<T: Trace> Trace for RefCell<T> {
+        // Panics when value is mutably borrowed during collection
+        fn trace(&self, tracer: &mut Tracer) {
+            self.borrow().trace(tracer);
+        }
+    }
+
+    impl<T: Trace> Trace for Vec<T> {
+        fn trace(&self, tracer: &mut Tracer) {
+            for value in self {
+                value.trace(tracer);
+            }
+        }
+    }
+
+    impl<T: Trace> Trace for crate::vec::vec::Vec<T> {
+        fn trace(&self, tracer: &mut Tracer) {
+            for value in self {
+                value.trace(tracer);
+            }
+        }
+    }
+
+    impl<T: Trace> Trace for crate::boxed::boxed::Box<T> {
+        fn trace(&self, tracer: &mut Tracer) {
+            (**self).trace(tracer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gc::gc::{collect_cycles, Gc, Trace, Tracer};
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    struct Node {
+        next: RefCell<Option<Gc<Node>>>,
+        drops: Rc<Cell<usize>>,
+    }
+
+    impl Node {
+        fn new(drops: &Rc<Cell<usize>>) -> Gc<Node> {
+            Gc::new(Node {
+                next: RefCell::new(None),
+                drops: Rc::clone(drops),
+            })
+        }
+
+        fn link(&self, next: &Gc<Node>) {
+            *self.next.borrow_mut() = Some(next.clone());
+        }
+    }
+
+    impl Trace for Node {
+        fn trace(&self, tracer: &mut Tracer) {
+            self.next.trace(tracer);
+        }
+    }
+
+    impl Drop for Node {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+        }
+    }
+
+    #[test]
+    fn should_free_acyclic_value_without_collection() {
+        let drops = Rc::new(Cell::new(0));
+        let first = Node::new(&drops);
+        let second = Node::new(&drops);
+        first.link(&second);
+
+        drop(second);
+        drop(first);
+
+        assert_eq!(drops.get(), 2);
+        collect_cycles();
+    }
+
+    #[test]
+    fn should_collect_self_loop() {
+        let drops = Rc::new(Cell::new(0));
+        let node = Node::new(&drops);
+        node.link(&node);
+
+        drop(node);
+
+        assert_eq!(drops.get(), 0);
+        collect_cycles();
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn should_collect_two_node_cycle() {
+        let drops = Rc::new(Cell::new(0));
+        let first = Node::new(&drops);
+        let second = Node::new(&drops);
+        first.link(&second);
+        second.link(&first);
+
+        drop(first);
+        drop(second);
+
+        assert_eq!(drops.get(), 0);
+        collect_cycles();
+        assert_eq!(drops.get(), 2);
+    }
+
+    #[test]
+    fn should_keep_cycle_reachable_from_live_root() {
+        let drops = Rc::new(Cell::new(0));
+        let root = Node::new(&drops);
+        let first = Node::new(&drops);
+        let second = Node::new(&drops);
+        root.link(&first);
+        first.link(&second);
+        second.link(&first);
+
+        drop(first);
+        drop(second);
+        collect_cycles();
+
+        let first = root.next.borrow().clone().unwrap();
+        assert_eq!(drops.get(), 0);
+        assert_eq!(Gc::strong_count(&first), 3);
+        assert_eq!(first.next.borrow().is_some(), true);
+
+        drop(first);
+        drop(root);
+        collect_cycles();
+
+        assert_eq!(drops.get(), 3);
+    }
+
+    struct Fork {
+        next: RefCell<Option<Gc<Fork>>>,
+        leaf: Gc<Node>,
+    }
+
+    impl Trace for Fork {
+        fn trace(&self, tracer: &mut Tracer) {
+            self.next.trace(tracer);
+            self.leaf.trace(tracer);
+        }
+    }
+
+    #[test]
+    fn should_keep_live_value_referenced_from_collected_cycle() {
+        let drops = Rc::new(Cell::new(0));
+        let leaf = Node::new(&drops);
+        let first = Gc::new(Fork {
+            next: RefCell::new(None),
+            leaf: leaf.clone(),
+        });
+        let second = Gc::new(Fork {
+            next: RefCell::new(Some(first.clone())),
+            leaf: leaf.clone(),
+        });
+        *first.next.borrow_mut() = Some(second.clone());
+
+        drop(first);
+        drop(second);
+        assert_eq!(Gc::strong_count(&leaf), 3);
+        collect_cycles();
+
+        assert_eq!(Gc::strong_count(&leaf), 1);
+        assert_eq!(drops.get(), 0);
+        assert_eq!(leaf.next.borrow().is_none(), true);
+
+        drop(leaf);
+
+        assert_eq!(drops.get(), 1);
+        collect_cycles();
+    }
+
+    #[test]
+    fn should_free_buffered_value_released_before_collection() {
+        let drops = Rc::new(Cell::new(0));
+        let node = Node::new(&drops);
+        let other = node.clone();
+
+        drop(other);
+        drop(node);
+
+        assert_eq!(drops.get(), 1);
+        collect_cycles();
+        assert_eq!(drops.get(), 1);
+    }
+}
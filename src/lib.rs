@@ -1,5 +1,21 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![allow(clippy::module_inception)]
+#![cfg_attr(test, allow(clippy::bool_assert_comparison))]
+
+extern crate alloc;
+
+pub mod array_vec;
 pub mod boxed;
 pub mod either;
+pub mod either_or_both;
+// Roots of possible cycles are kept in a thread local
+#[cfg(feature = "std")]
+pub mod gc;
 pub mod macros;
 pub mod rc;
+pub mod small_vec;
+#[cfg(test)]
+mod tracked;
+pub mod validated;
 pub mod vec;
+pub mod vec_deque;
@@ -1,232 +1,562 @@
 pub mod rc {
-    use std::alloc::Layout;
-    use std::alloc::{alloc, dealloc};
-    use std::fmt::{Debug, Display, Formatter};
-    use std::ops::Deref;
-    use std::ptr::null_mut;
+    use crate::boxed::boxed::Box;
+    use crate::vec::vec::Vec;
+    use alloc::alloc::Layout;
+    use alloc::alloc::{alloc, dealloc, handle_alloc_error};
+    use alloc::string::String;
+    use core::any::Any;
+    use core::borrow::Borrow;
+    use core::cmp::Ordering;
+    use core::fmt::{Debug, Display, Formatter};
+    use core::hash::{Hash, Hasher};
+    use core::mem;
+    use core::ops::Deref;
+    use core::ptr::{self, null_mut};
 
-    #[derive(Clone, Debug)]
-    struct Counter(*mut usize, *mut usize);
+    // (strong, weak) counters stored in front of the value.
+    // Weak counter includes one implicit reference shared by all strong references,
+    // so the allocation is released only after both the value and the last Weak are gone.
+    #[derive(Clone, Copy, Debug)]
+    struct Counter(usize, usize);
 
     impl Counter {
-        fn new() -> Self {
-            unsafe {
-                let strong_counter = alloc(Self::counter_layout()) as *mut usize;
-                let weak_counter = alloc(Self::counter_layout()) as *mut usize;
-                Counter(strong_counter, weak_counter)
-            }
+        fn inc_strong(&mut self) {
+            self.0 += 1;
         }
 
-        fn empty() -> Self {
-            Counter(null_mut(), null_mut())
+        fn inc_weak(&mut self) {
+            self.1 += 1;
         }
 
-        fn counter_layout() -> Layout {
-            Layout::new::<usize>()
+        fn dec_strong(&mut self) {
+            self.0 -= 1;
         }
 
-        unsafe fn inc_strong(&self) {
-            if !self.0.is_null() {
-                *self.0 += 1;
-            }
+        fn dec_weak(&mut self) {
+            self.1 -= 1;
+        }
+
+        fn strong(&self) -> usize {
+            self.0
         }
 
-        unsafe fn inc_weak(&self) {
-            if !self.1.is_null() {
-                *self.1 += 1;
+        // Number of Weak references, without the implicit one held by strong references
+        fn weak(&self) -> usize {
+            if self.is_strong_positive() {
+                self.1 - 1
+            } else {
+                self.1
             }
         }
 
-        unsafe fn dec_strong(&self) {
-            if !self.0.is_null() {
-                *self.0 -= 1;
+        fn is_strong_positive(&self) -> bool {
+            self.0 > 0
+        }
+    }
+
+    impl Display for Counter {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            write!(f, "({}, {})", self.0, self.1)
+        }
+    }
+
+    // Layout always has the counter, so it is never zero-sized
+    fn allocate(layout: Layout) -> *mut u8 {
+        let ptr = unsafe { alloc(layout) };
+        if ptr.is_null() {
+            handle_alloc_error(layout);
+        }
+        ptr
+    }
+
+    // Allocation layout is kept next to the counter, the last Weak releases it
+    // after the value is dropped and its size can't be read anymore
+    #[repr(C)]
+    struct RcBox<T: ?Sized> {
+        counter: Counter,
+        layout: Layout,
+        value: T,
+    }
+
+    impl<T: ?Sized> RcBox<T> {
+        // Layout of RcBox holding value with given layout
+        fn layout(value: Layout) -> Layout {
+            Self::header().extend(value).unwrap().0.pad_to_align()
+        }
+
+        // Counter and layout in front of the value, same as RcBox<()>
+        fn header() -> Layout {
+            Layout::new::<RcBox<()>>()
+        }
+
+        // Recovers RcBox pointer from pointer to its live value
+        unsafe fn from_value(ptr: *const T) -> *mut RcBox<T> {
+            let offset = Self::header().extend(Layout::for_value(&*ptr)).unwrap().1;
+            ptr.byte_sub(offset) as *mut RcBox<T>
+        }
+
+        // Drops one weak reference and frees the allocation when it was the last one
+        unsafe fn release_weak(this: *mut Self) {
+            (*this).counter.dec_weak();
+            if (*this).counter.1 == 0 {
+                dealloc(this as *mut u8, (*this).layout);
             }
         }
+    }
+
+    // Coerces Rc<T> into Rc<U> for any U that *const T coerces to, like dyn Trait.
+    // Allocation is kept, only metadata is added to the pointer.
+    #[macro_export]
+    macro_rules! rc_unsize {
+        ($rc:expr => $target:ty) => {{
+            let ptr = $crate::rc::rc::Rc::into_raw($rc);
+            let ptr: *const $target = ptr;
+            unsafe { $crate::rc::rc::Rc::<$target>::from_raw(ptr) }
+        }};
+    }
 
-        unsafe fn dec_weak(&self) {
-            if !self.1.is_null() {
-                *self.1 -= 1;
+    // Counters are not atomic, raw pointer keeps Rc and Weak neither Send nor Sync
+    /// ```compile_fail,E0277
+    /// fn assert_send<T: Send>() {}
+    /// assert_send::<rust_samples::rc::rc::Rc<i32>>();
+    /// ```
+    ///
+    /// ```compile_fail,E0277
+    /// fn assert_sync<T: Sync>() {}
+    /// assert_sync::<rust_samples::rc::rc::Rc<i32>>();
+    /// ```
+    ///
+    /// ```compile_fail,E0277
+    /// let rc = rust_samples::rc::rc::Rc::new(1);
+    /// std::thread::spawn(move || *rc + 1).join().unwrap();
+    /// ```
+    pub struct Rc<T: ?Sized>(*mut RcBox<T>);
+
+    impl<T> Rc<T> {
+        pub fn new(value: T) -> Self {
+            unsafe {
+                let ptr = allocate(Self::layout()) as *mut RcBox<T>;
+                ptr.write(RcBox {
+                    counter: Counter(1, 1),
+                    layout: Self::layout(),
+                    value,
+                });
+                Rc(ptr)
             }
         }
 
-        fn is_strong_positive(&self) -> bool {
+        // Weak passed to data_fn points to the allocation being built, upgrade returns None until new_cyclic returns
+        pub fn new_cyclic<F>(data_fn: F) -> Self
+        where
+            F: FnOnce(&Weak<T>) -> T,
+        {
             unsafe {
-                if !self.0.is_null() {
-                    *self.0 > 0
-                } else {
-                    false
-                }
+                let ptr = allocate(Self::layout()) as *mut RcBox<T>;
+                ptr::addr_of_mut!((*ptr).counter).write(Counter(0, 1));
+                ptr::addr_of_mut!((*ptr).layout).write(Self::layout());
+                let weak = Weak(ptr);
+                let value = data_fn(&weak);
+                ptr::addr_of_mut!((*ptr).value).write(value);
+                (*ptr).counter.inc_strong();
+                // Weak reference becomes the implicit one held by strong references
+                mem::forget(weak);
+                Rc(ptr)
             }
         }
+
+        fn layout() -> Layout {
+            Layout::new::<RcBox<T>>()
+        }
     }
 
-    impl Display for Counter {
-        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    impl<T: ?Sized> Rc<T> {
+        pub fn strong_count(this: &Self) -> usize {
+            unsafe { (*this.0).counter.strong() }
+        }
+
+        pub fn weak_count(this: &Self) -> usize {
+            unsafe { (*this.0).counter.weak() }
+        }
+
+        pub fn downgrade(this: &Self) -> Weak<T> {
             unsafe {
-                let strong = if self.0.is_null() {
-                    String::from("null")
-                } else {
-                    (*self.0).to_string()
-                };
-                let weak = if self.1.is_null() {
-                    String::from("null")
-                } else {
-                    (*self.1).to_string()
-                };
-                write!(f, "({}, {})", strong, weak)
+                (*this.0).counter.inc_weak();
+                Weak(this.0)
             }
         }
+
+        // Compares allocations, not values like ==
+        pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+            ptr::addr_eq(this.0, other.0)
+        }
+
+        pub fn as_ptr(this: &Self) -> *const T {
+            unsafe { ptr::addr_of!((*this.0).value) }
+        }
+
+        // Strong reference is kept alive until it is passed back to Rc::from_raw
+        pub fn into_raw(this: Self) -> *const T {
+            let ptr = Self::as_ptr(&this);
+            mem::forget(this);
+            ptr
+        }
+
+        /// # Safety
+        ///
+        /// `ptr` must come from `Rc::into_raw` or `Rc::as_ptr` of a live Rc, possibly coerced
+        /// to an unsized type. It takes over a strong reference no Rc owns, like the one kept by
+        /// `into_raw` or added by `increment_strong_count`, and each such reference is taken once.
+        pub unsafe fn from_raw(ptr: *const T) -> Self {
+            Rc(RcBox::from_value(ptr))
+        }
+
+        /// # Safety
+        ///
+        /// `ptr` must come from `Rc::into_raw` or `Rc::as_ptr` of a live Rc, and the allocation
+        /// must still have a strong reference.
+        pub unsafe fn increment_strong_count(ptr: *const T) {
+            (*RcBox::from_value(ptr)).counter.inc_strong();
+        }
+
+        /// # Safety
+        ///
+        /// `ptr` must come from `Rc::into_raw` or `Rc::as_ptr` of a live Rc. It releases a strong
+        /// reference no Rc owns, like the one kept by `into_raw` or added by `increment_strong_count`.
+        pub unsafe fn decrement_strong_count(ptr: *const T) {
+            drop(Rc::from_raw(ptr));
+        }
     }
 
-    impl Drop for Counter {
-        fn drop(&mut self) {
+    impl<T> Rc<[T]> {
+        // Allocates storage for len elements, elements are not initialized
+        unsafe fn allocate_slice(len: usize) -> *mut RcBox<[T]> {
+            let layout = RcBox::<[T]>::layout(Layout::array::<T>(len).unwrap());
+            let ptr = allocate(layout);
+            ptr.cast::<RcBox<()>>().write(RcBox {
+                counter: Counter(1, 1),
+                layout,
+                value: (),
+            });
+            ptr::slice_from_raw_parts_mut(ptr as *mut T, len) as *mut RcBox<[T]>
+        }
+
+        unsafe fn elements(ptr: *mut RcBox<[T]>) -> *mut T {
+            ptr::addr_of_mut!((*ptr).value) as *mut T
+        }
+    }
+
+    impl Rc<dyn Any> {
+        pub fn downcast<T: Any>(self) -> Result<Rc<T>, Self> {
+            if (*self).is::<T>() {
+                let result = Rc(self.0 as *mut RcBox<T>);
+                mem::forget(self);
+                Ok(result)
+            } else {
+                Err(self)
+            }
+        }
+    }
+
+    impl<T: Clone> From<&[T]> for Rc<[T]> {
+        fn from(value: &[T]) -> Self {
             unsafe {
-                if !(self.0.is_null() && self.1.is_null()) {
-                    if *self.0 == 0 && *self.1 == 0 {
-                        dealloc(self.0 as *mut u8, Self::counter_layout());
-                        dealloc(self.1 as *mut u8, Self::counter_layout());
-                    }
+                let ptr = Self::allocate_slice(value.len());
+                let elements = Self::elements(ptr);
+                for (i, elem) in value.iter().enumerate() {
+                    elements.add(i).write(elem.clone());
                 }
+                Rc(ptr)
             }
         }
     }
 
-    #[derive(Debug)]
-    pub struct Rc<T>(*mut T, Counter);
-
-    impl<T> Rc<T> {
-        pub fn new(value: T) -> Self {
+    impl<T> From<Vec<T>> for Rc<[T]> {
+        fn from(mut value: Vec<T>) -> Self {
             unsafe {
-                let ptr = alloc(Self::layout()) as *mut T;
-                let counter = Counter::new();
-                *ptr = value;
-                counter.inc_strong();
-                Rc(ptr, counter)
+                let ptr = Self::allocate_slice(value.len());
+                value
+                    .as_ptr()
+                    .copy_to_nonoverlapping(Self::elements(ptr), value.len());
+                // Elements are moved out, vector only releases its buffer
+                value.set_len(0);
+                Rc(ptr)
             }
         }
+    }
 
-        pub fn strong_count(this: &Self) -> usize {
-            unsafe { *this.1 .0 }
+    impl<T> FromIterator<T> for Rc<[T]> {
+        fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+            Self::from(iter.into_iter().collect::<Vec<T>>())
         }
+    }
 
-        pub fn weak_count(this: &Self) -> usize {
-            unsafe { *this.1 .1 }
+    impl From<&str> for Rc<str> {
+        fn from(value: &str) -> Self {
+            let bytes: Rc<[u8]> = Rc::from(value.as_bytes());
+            let result = Rc(bytes.0 as *mut RcBox<str>);
+            mem::forget(bytes);
+            result
         }
+    }
 
-        fn layout() -> Layout {
-            Layout::new::<T>()
+    impl From<String> for Rc<str> {
+        fn from(value: String) -> Self {
+            Rc::from(value.as_str())
         }
+    }
 
-        pub fn clone(old: &Self) -> Self {
-            old.clone()
+    // Moves boxed value into new allocation, use rc_unsize! to get Rc<dyn Trait>
+    impl<T> From<alloc::boxed::Box<T>> for Rc<T> {
+        fn from(value: alloc::boxed::Box<T>) -> Self {
+            Rc::new(*value)
         }
+    }
 
-        pub fn downgrade(this: &Self) -> Weak<T> {
+    impl<T> From<alloc::boxed::Box<[T]>> for Rc<[T]> {
+        fn from(value: alloc::boxed::Box<[T]>) -> Self {
             unsafe {
-                this.1.inc_weak();
-                Weak(this.0, this.1.clone())
+                let len = value.len();
+                let ptr = Self::allocate_slice(len);
+                let raw = alloc::boxed::Box::into_raw(value) as *mut [mem::ManuallyDrop<T>];
+                (raw as *const T).copy_to_nonoverlapping(Self::elements(ptr), len);
+                // Elements are moved out, box only releases its buffer
+                drop(alloc::boxed::Box::from_raw(raw));
+                Rc(ptr)
             }
         }
     }
 
-    impl<T: Eq> PartialEq<Self> for Rc<T> {
+    impl From<alloc::boxed::Box<str>> for Rc<str> {
+        fn from(value: alloc::boxed::Box<str>) -> Self {
+            Rc::from(&*value)
+        }
+    }
+
+    impl<T> From<T> for Rc<T> {
+        fn from(value: T) -> Self {
+            Rc::new(value)
+        }
+    }
+
+    impl<T> From<Box<T>> for Rc<T> {
+        fn from(value: Box<T>) -> Self {
+            Rc::new(Box::into_inner(value))
+        }
+    }
+
+    impl<T: Default> Default for Rc<T> {
+        fn default() -> Self {
+            Rc::new(T::default())
+        }
+    }
+
+    impl<T: PartialEq + ?Sized> PartialEq<Self> for Rc<T> {
         fn eq(&self, other: &Self) -> bool {
-            unsafe { *self.0 == *other.0 }
+            **self == **other
         }
     }
 
-    impl<T: Eq> Eq for Rc<T> {}
+    impl<T: Eq + ?Sized> Eq for Rc<T> {}
 
-    impl<T: Display> Display for Rc<T> {
-        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            unsafe {
-                let value = if self.0.is_null() {
-                    String::from("null")
-                } else {
-                    (*self.0).to_string()
-                };
-                write!(f, "({}, {})", value, self.1)
-            }
+    impl<T: PartialOrd + ?Sized> PartialOrd<Self> for Rc<T> {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            (**self).partial_cmp(&**other)
+        }
+    }
+
+    impl<T: Ord + ?Sized> Ord for Rc<T> {
+        fn cmp(&self, other: &Self) -> Ordering {
+            (**self).cmp(&**other)
         }
     }
 
-    impl<T> Deref for Rc<T> {
+    impl<T: Hash + ?Sized> Hash for Rc<T> {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            (**self).hash(state)
+        }
+    }
+
+    impl<T: Debug + ?Sized> Debug for Rc<T> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            Debug::fmt(&**self, f)
+        }
+    }
+
+    // Values are serialized, so shared references are not preserved
+    #[cfg(feature = "serde")]
+    impl<T: serde::Serialize + ?Sized> serde::Serialize for Rc<T> {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            (**self).serialize(serializer)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Rc<T> {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            T::deserialize(deserializer).map(Rc::new)
+        }
+    }
+
+    impl<T: Display + ?Sized> Display for Rc<T> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            unsafe { write!(f, "({}, {})", &(*self.0).value, (*self.0).counter) }
+        }
+    }
+
+    impl<T: ?Sized> Deref for Rc<T> {
         type Target = T;
 
         fn deref(&self) -> &Self::Target {
-            unsafe { &*self.0 }
+            unsafe { &(*self.0).value }
         }
     }
 
-    impl<T> Clone for Rc<T> {
+    impl<T: ?Sized> AsRef<T> for Rc<T> {
+        fn as_ref(&self) -> &T {
+            self
+        }
+    }
+
+    impl<T: ?Sized> Borrow<T> for Rc<T> {
+        fn borrow(&self) -> &T {
+            self
+        }
+    }
+
+    impl<T: ?Sized> Clone for Rc<T> {
         fn clone(&self) -> Self {
             unsafe {
-                self.1.inc_strong();
-                Rc(self.0, self.1.clone())
+                (*self.0).counter.inc_strong();
+                Rc(self.0)
             }
         }
     }
 
-    impl<T> Drop for Rc<T> {
+    impl<T: ?Sized> Drop for Rc<T> {
         fn drop(&mut self) {
             unsafe {
-                self.1.dec_strong();
-                if !self.1.is_strong_positive() {
-                    dealloc(self.0 as *mut u8, Rc::<T>::layout());
+                (*self.0).counter.dec_strong();
+                if !(*self.0).counter.is_strong_positive() {
+                    ptr::drop_in_place(ptr::addr_of_mut!((*self.0).value));
+                    RcBox::release_weak(self.0);
                 }
             }
         }
     }
 
-    pub struct Weak<T>(*mut T, Counter);
+    // Weak created with Weak::new has null pointer and no allocation
+    /// ```compile_fail,E0277
+    /// fn assert_send<T: Send>() {}
+    /// assert_send::<rust_samples::rc::rc::Weak<i32>>();
+    /// ```
+    pub struct Weak<T: ?Sized>(*mut RcBox<T>);
 
     impl<T> Weak<T> {
         pub fn new() -> Self {
-            Weak(null_mut(), Counter::empty())
+            Weak(null_mut())
+        }
+
+        // Null for Weak::new, value may be already dropped
+        pub fn as_ptr(&self) -> *const T {
+            if self.0.is_null() {
+                ptr::null()
+            } else {
+                unsafe { ptr::addr_of!((*self.0).value) }
+            }
+        }
+
+        // Weak reference is kept alive until it is passed back to Weak::from_raw
+        pub fn into_raw(self) -> *const T {
+            let ptr = self.as_ptr();
+            mem::forget(self);
+            ptr
+        }
+
+        /// # Safety
+        ///
+        /// `ptr` must come from `Weak::into_raw` and each returned pointer may be passed back only once.
+        pub unsafe fn from_raw(ptr: *const T) -> Self {
+            if ptr.is_null() {
+                Weak::new()
+            } else {
+                Weak(ptr.byte_sub(mem::offset_of!(RcBox<T>, value)) as *mut RcBox<T>)
+            }
+        }
+    }
+
+    impl<T: ?Sized> Weak<T> {
+        pub fn ptr_eq(&self, other: &Self) -> bool {
+            ptr::addr_eq(self.0, other.0)
+        }
+
+        fn counter(&self) -> Option<Counter> {
+            if self.0.is_null() {
+                None
+            } else {
+                unsafe { Some((*self.0).counter) }
+            }
         }
 
         pub fn strong_count(&self) -> usize {
-            unsafe { *self.1 .0 }
+            self.counter().map_or(0, |counter| counter.strong())
         }
 
         pub fn weak_count(&self) -> usize {
-            unsafe { *self.1 .1 }
+            self.counter().map_or(0, |counter| counter.weak())
         }
 
         pub fn upgrade(&self) -> Option<Rc<T>> {
-            unsafe {
-                if self.1.is_strong_positive() {
-                    self.1.inc_strong();
-                    Some(Rc(self.0, self.1.clone()))
-                } else {
-                    None
+            if self.strong_count() > 0 {
+                unsafe {
+                    (*self.0).counter.inc_strong();
+                    Some(Rc(self.0))
                 }
+            } else {
+                None
             }
         }
     }
 
-    impl<T> Drop for Weak<T> {
+    impl<T> Default for Weak<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<T: ?Sized> Drop for Weak<T> {
         fn drop(&mut self) {
-            unsafe {
-                self.1.dec_weak();
+            if !self.0.is_null() {
+                unsafe {
+                    RcBox::release_weak(self.0);
+                }
             }
         }
     }
 
-    impl<T> Clone for Weak<T> {
+    impl<T: ?Sized> Clone for Weak<T> {
         fn clone(&self) -> Self {
-            unsafe {
-                self.1.inc_weak();
-                Weak(self.0, self.1.clone())
+            if !self.0.is_null() {
+                unsafe {
+                    (*self.0).counter.inc_weak();
+                }
             }
+            Weak(self.0)
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::boxed::boxed;
     use crate::rc::rc;
     use crate::rc::rc::{Rc, Weak};
+    use crate::rc_unsize;
+    use crate::vec::vec::{vec, Vec};
+    use std::any::Any;
+    use std::cell::RefCell;
+    use std::collections::{BTreeSet, HashMap, HashSet};
+    use std::ffi::c_void;
+    use std::fmt::Display;
 
     #[test]
     fn should_dereference_properly() {
@@ -294,4 +624,403 @@ mod tests {
 
         assert_eq!(new_reference.is_some(), true);
     }
+
+    #[test]
+    fn should_not_upgrade_during_cyclic_construction() {
+        let reference = Rc::new_cyclic(|weak: &Weak<i32>| {
+            assert_eq!(weak.upgrade().is_none(), true);
+            assert_eq!(weak.strong_count(), 0);
+            7
+        });
+
+        assert_eq!(*reference, 7);
+        assert_eq!(Rc::strong_count(&reference), 1);
+        assert_eq!(Rc::weak_count(&reference), 0);
+    }
+
+    #[test]
+    fn should_upgrade_weak_created_in_cyclic_construction() {
+        struct SelfReferencing {
+            this: Weak<SelfReferencing>,
+            value: i32,
+        }
+
+        let reference = Rc::new_cyclic(|weak| SelfReferencing {
+            this: weak.clone(),
+            value: 7,
+        });
+
+        let upgraded = reference.this.upgrade().unwrap();
+
+        assert_eq!(upgraded.value, 7);
+        assert_eq!(Rc::strong_count(&reference), 2);
+        assert_eq!(Rc::weak_count(&reference), 1);
+    }
+
+    struct TreeNode {
+        value: i32,
+        parent: Weak<TreeNode>,
+        children: RefCell<std::vec::Vec<Rc<TreeNode>>>,
+    }
+
+    impl TreeNode {
+        fn new(value: i32, parent: &Weak<TreeNode>, children: &[i32]) -> Rc<TreeNode> {
+            Rc::new_cyclic(|this| TreeNode {
+                value,
+                parent: parent.clone(),
+                children: RefCell::new(
+                    children
+                        .iter()
+                        .map(|&child| TreeNode::new(child, this, &[]))
+                        .collect(),
+                ),
+            })
+        }
+    }
+
+    #[test]
+    fn should_build_doubly_linked_tree() {
+        let root = TreeNode::new(1, &Weak::new(), &[2, 3]);
+
+        let children = root.children.borrow();
+        let parent = children[1].parent.upgrade().unwrap();
+
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].value, 2);
+        assert_eq!(children[1].value, 3);
+        assert_eq!(parent.value, 1);
+        assert_eq!(root.parent.upgrade().is_none(), true);
+        assert_eq!(Rc::weak_count(&root), 2);
+    }
+
+    #[test]
+    fn should_release_parent_links_when_tree_dropped() {
+        let root = TreeNode::new(1, &Weak::new(), &[2]);
+        let child = Rc::clone(&root.children.borrow()[0]);
+
+        drop(root);
+
+        assert_eq!(child.parent.upgrade().is_none(), true);
+        assert_eq!(child.parent.strong_count(), 0);
+        assert_eq!(child.parent.weak_count(), 1);
+    }
+
+    #[test]
+    fn should_create_str_from_literal() {
+        let uat: Rc<str> = Rc::from("Hello");
+        let second = Rc::clone(&uat);
+
+        assert_eq!(&*uat, "Hello");
+        assert_eq!(uat, second);
+        assert_eq!(Rc::strong_count(&uat), 2);
+    }
+
+    #[test]
+    fn should_create_str_from_string() {
+        let uat: Rc<str> = Rc::from(String::from("Hello"));
+
+        assert_eq!(uat.len(), 5);
+        assert_eq!(uat.to_uppercase(), "HELLO");
+    }
+
+    #[test]
+    fn should_create_empty_str() {
+        let uat: Rc<str> = Rc::from("");
+
+        assert_eq!(uat.is_empty(), true);
+    }
+
+    #[test]
+    fn should_create_slice_from_slice() {
+        let uat: Rc<[i32]> = Rc::from(&[1, 2, 3][..]);
+
+        assert_eq!(uat.len(), 3);
+        assert_eq!(uat[2], 3);
+    }
+
+    #[test]
+    fn should_create_slice_from_vec() {
+        let vec: Vec<String> = vec![String::from("a"), String::from("b")];
+
+        let uat: Rc<[String]> = Rc::from(vec);
+
+        assert_eq!(uat.len(), 2);
+        assert_eq!(uat[0], "a");
+        assert_eq!(uat[1], "b");
+    }
+
+    #[test]
+    fn should_collect_slice_from_iterator() {
+        let uat: Rc<[String]> = (1..=5).map(|x| x.to_string()).collect();
+
+        assert_eq!(uat.len(), 5);
+        assert_eq!(uat.concat(), "12345");
+    }
+
+    #[test]
+    fn should_hold_trait_object() {
+        let uat = rc_unsize!(Rc::new(7) => dyn Display);
+        let weak = Rc::downgrade(&uat);
+
+        assert_eq!((*uat).to_string(), "7");
+        assert_eq!((*weak.upgrade().unwrap()).to_string(), "7");
+    }
+
+    #[test]
+    fn should_downcast_to_concrete_type() {
+        let uat = rc_unsize!(Rc::new(String::from("Hello")) => dyn Any);
+
+        let result = uat.downcast::<String>();
+
+        assert_eq!(result.is_ok(), true);
+        assert_eq!(*result.unwrap(), "Hello");
+    }
+
+    #[test]
+    fn should_not_downcast_to_other_type() {
+        let uat = rc_unsize!(Rc::new(7) => dyn Any);
+
+        let result = uat.downcast::<String>();
+
+        assert_eq!(result.is_err(), true);
+        assert_eq!(result.unwrap_err().downcast_ref::<i32>(), Some(&7));
+    }
+
+    #[test]
+    fn should_move_boxed_slice() {
+        let value: std::boxed::Box<[String]> =
+            std::boxed::Box::new([String::from("a"), String::from("b")]);
+
+        let uat: Rc<[String]> = Rc::from(value);
+
+        assert_eq!(uat.len(), 2);
+        assert_eq!(uat[1], "b");
+    }
+
+    #[test]
+    fn should_move_boxed_str() {
+        let uat: Rc<str> = Rc::from(std::boxed::Box::<str>::from("Hello"));
+
+        assert_eq!(&*uat, "Hello");
+    }
+
+    #[test]
+    fn should_keep_counts_when_unsized() {
+        let uat = Rc::new(7);
+        let weak = Rc::downgrade(&uat);
+
+        let uat = rc_unsize!(uat => dyn Display);
+
+        assert_eq!(Rc::strong_count(&uat), 1);
+        assert_eq!(Rc::weak_count(&uat), 1);
+        assert_eq!(*weak.upgrade().unwrap(), 7);
+    }
+
+    #[test]
+    fn should_release_slice_from_last_weak() {
+        let uat: Rc<[String]> = Rc::from(&[String::from("a"), String::from("b")][..]);
+        let weak = Rc::downgrade(&uat);
+
+        drop(uat);
+
+        assert_eq!(weak.upgrade().is_none(), true);
+        assert_eq!(weak.weak_count(), 1);
+    }
+
+    #[test]
+    fn should_compare_identity_not_value() {
+        let first = Rc::new(7);
+        let second = Rc::new(7);
+        let third = Rc::clone(&first);
+
+        assert_eq!(first == second, true);
+        assert_eq!(Rc::ptr_eq(&first, &second), false);
+        assert_eq!(Rc::ptr_eq(&first, &third), true);
+    }
+
+    #[test]
+    fn should_compare_weak_identity() {
+        let first = Rc::new(7);
+        let second = Rc::new(7);
+
+        assert_eq!(Rc::downgrade(&first).ptr_eq(&Rc::downgrade(&first)), true);
+        assert_eq!(Rc::downgrade(&first).ptr_eq(&Rc::downgrade(&second)), false);
+        assert_eq!(Weak::<i32>::new().ptr_eq(&Weak::new()), true);
+    }
+
+    #[test]
+    fn should_point_to_value() {
+        let uat = Rc::new(7);
+
+        assert_eq!(Rc::as_ptr(&uat), &*uat as *const i32);
+    }
+
+    #[test]
+    fn should_keep_reference_through_raw_pointer() {
+        let uat = Rc::new(String::from("Hello"));
+        let weak = Rc::downgrade(&uat);
+
+        let raw = Rc::into_raw(uat);
+
+        assert_eq!(weak.strong_count(), 1);
+        assert_eq!(unsafe { &*raw }, "Hello");
+
+        let uat = unsafe { Rc::from_raw(raw) };
+
+        assert_eq!(*uat, "Hello");
+        assert_eq!(Rc::strong_count(&uat), 1);
+    }
+
+    #[test]
+    fn should_restore_unsized_from_raw_pointer() {
+        let uat: Rc<str> = Rc::from("Hello");
+
+        let uat = unsafe { Rc::from_raw(Rc::into_raw(uat)) };
+
+        assert_eq!(&*uat, "Hello");
+    }
+
+    #[test]
+    fn should_change_strong_count_through_raw_pointer() {
+        let uat = Rc::new(7);
+        let raw = Rc::as_ptr(&uat);
+
+        unsafe { Rc::increment_strong_count(raw) };
+
+        assert_eq!(Rc::strong_count(&uat), 2);
+
+        unsafe { Rc::decrement_strong_count(raw) };
+
+        assert_eq!(Rc::strong_count(&uat), 1);
+    }
+
+    #[test]
+    fn should_pass_through_c_callback() {
+        extern "C" fn callback(data: *const c_void) -> i32 {
+            let value = unsafe { Rc::from_raw(data as *const i32) };
+            *value * 2
+        }
+
+        let uat = Rc::new(7);
+        unsafe { Rc::increment_strong_count(Rc::as_ptr(&uat)) };
+
+        let result = callback(Rc::as_ptr(&uat) as *const c_void);
+
+        assert_eq!(result, 14);
+        assert_eq!(Rc::strong_count(&uat), 1);
+    }
+
+    #[test]
+    fn should_keep_weak_through_raw_pointer() {
+        let uat = Rc::new(7);
+        let raw = Rc::downgrade(&uat).into_raw();
+
+        assert_eq!(raw, Rc::as_ptr(&uat));
+        assert_eq!(Rc::weak_count(&uat), 1);
+
+        let weak = unsafe { Weak::from_raw(raw) };
+
+        assert_eq!(*weak.upgrade().unwrap(), 7);
+        drop(weak);
+        assert_eq!(Rc::weak_count(&uat), 0);
+    }
+
+    #[test]
+    fn should_restore_empty_weak_from_raw_pointer() {
+        let raw = Weak::<i32>::new().into_raw();
+
+        let weak = unsafe { Weak::from_raw(raw) };
+
+        assert_eq!(raw.is_null(), true);
+        assert_eq!(weak.upgrade().is_none(), true);
+    }
+
+    #[test]
+    fn should_compare_partially_ordered_values() {
+        let first = Rc::new(1.5);
+        let second = Rc::new(2.5);
+
+        assert_eq!(first, Rc::new(1.5));
+        assert_eq!(first < second, true);
+        assert_eq!(Rc::new(f64::NAN) == Rc::new(f64::NAN), false);
+    }
+
+    #[test]
+    fn should_order_values() {
+        let set: BTreeSet<Rc<i32>> = [3, 1, 2].into_iter().map(Rc::new).collect();
+
+        let result: std::vec::Vec<i32> = set.iter().map(|x| **x).collect();
+
+        assert_eq!(result, [1, 2, 3]);
+    }
+
+    #[test]
+    fn should_hash_value() {
+        let mut set = HashSet::new();
+
+        set.insert(Rc::new(String::from("Hello")));
+
+        assert_eq!(set.contains(&Rc::new(String::from("Hello"))), true);
+    }
+
+    #[test]
+    fn should_format_value_with_debug() {
+        let uat = Rc::new(String::from("Hello"));
+
+        assert_eq!(format!("{:?}", uat), "\"Hello\"");
+    }
+
+    #[test]
+    fn should_create_default() {
+        let uat: Rc<String> = Rc::default();
+
+        assert_eq!(uat.is_empty(), true);
+    }
+
+    #[test]
+    fn should_create_from_value() {
+        let uat: Rc<i32> = Rc::from(7);
+
+        assert_eq!(*uat, 7);
+    }
+
+    #[test]
+    fn should_create_from_box() {
+        let uat: Rc<String> = Rc::from(boxed::Box::new(String::from("Hello")));
+
+        assert_eq!(*uat, "Hello");
+    }
+
+    #[test]
+    fn should_look_up_by_borrowed_value() {
+        let mut map: HashMap<Rc<str>, i32> = HashMap::new();
+
+        map.insert(Rc::from("key"), 7);
+
+        assert_eq!(map.get("key"), Some(&7));
+    }
+
+    #[test]
+    fn should_return_reference_to_value() {
+        let uat = Rc::new(String::from("Hello"));
+
+        let result: &String = uat.as_ref();
+
+        assert_eq!(result, "Hello");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_round_trip_through_json() {
+        let uat = Rc::new(std::vec![1, 2]);
+        let shared = (Rc::clone(&uat), uat);
+
+        let json = serde_json::to_string(&shared).unwrap();
+        let result: (Rc<std::vec::Vec<i32>>, Rc<std::vec::Vec<i32>>) =
+            serde_json::from_str(&json).unwrap();
+
+        assert_eq!(json, "[[1,2],[1,2]]");
+        assert_eq!(result.0, result.1);
+        assert_eq!(Rc::ptr_eq(&result.0, &result.1), false);
+    }
 }
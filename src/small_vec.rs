@@ -0,0 +1,392 @@
+pub mod small_vec {
+    use crate::vec::vec::Vec;
+    use core::fmt::{Debug, Formatter};
+    use core::mem::{self, MaybeUninit};
+    use core::ops::{Deref, DerefMut};
+    use core::ptr;
+    use core::slice;
+
+    // Backing array of SmallVec, gives element type and inline capacity
+    /// # Safety
+    ///
+    /// `CAPACITY` must be the number of `Item`s the implementing type stores contiguously.
+    pub unsafe trait Array {
+        type Item;
+        const CAPACITY: usize;
+    }
+
+    unsafe impl<T, const N: usize> Array for [T; N] {
+        type Item = T;
+        const CAPACITY: usize = N;
+    }
+
+    enum Data<A: Array> {
+        // Only first len elements of buffer are initialized
+        Inline { len: usize, buffer: MaybeUninit<A> },
+        Heap(Vec<A::Item>),
+    }
+
+    // Stores up to A::CAPACITY elements inline, then moves them to vec::Vec
+    pub struct SmallVec<A: Array> {
+        data: Data<A>,
+    }
+
+    impl<A: Array> SmallVec<A> {
+        pub fn new() -> Self {
+            SmallVec {
+                data: Data::Inline {
+                    len: 0,
+                    buffer: MaybeUninit::uninit(),
+                },
+            }
+        }
+
+        pub fn len(&self) -> usize {
+            match &self.data {
+                Data::Inline { len, .. } => *len,
+                Data::Heap(vec) => vec.len(),
+            }
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        pub fn capacity(&self) -> usize {
+            match &self.data {
+                Data::Inline { .. } => A::CAPACITY,
+                Data::Heap(vec) => vec.allocated(),
+            }
+        }
+
+        // True once elements were moved to the heap, they stay there
+        pub fn spilled(&self) -> bool {
+            matches!(self.data, Data::Heap(_))
+        }
+
+        pub fn push(&mut self, value: A::Item) {
+            self.spill_if_full();
+            match &mut self.data {
+                Data::Inline { len, buffer } => {
+                    unsafe { (buffer.as_mut_ptr() as *mut A::Item).add(*len).write(value) };
+                    *len += 1;
+                }
+                Data::Heap(vec) => vec.push(value),
+            }
+        }
+
+        pub fn pop(&mut self) -> Option<A::Item> {
+            match &mut self.data {
+                Data::Inline { len: 0, .. } => None,
+                Data::Inline { len, buffer } => {
+                    *len -= 1;
+                    unsafe { Some((buffer.as_ptr() as *const A::Item).add(*len).read()) }
+                }
+                Data::Heap(vec) => vec.pop(),
+            }
+        }
+
+        pub fn insert(&mut self, index: usize, value: A::Item) {
+            let size = self.len();
+            if index > size {
+                panic!(
+                    "insertion index (is {}) should be <= len (is {})",
+                    index, size
+                );
+            }
+            self.spill_if_full();
+            match &mut self.data {
+                Data::Inline { len, buffer } => {
+                    unsafe {
+                        let slot = (buffer.as_mut_ptr() as *mut A::Item).add(index);
+                        slot.copy_to(slot.add(1), *len - index);
+                        slot.write(value);
+                    }
+                    *len += 1;
+                }
+                Data::Heap(vec) => vec.insert(index, value),
+            }
+        }
+
+        pub fn remove(&mut self, index: usize) -> A::Item {
+            match &mut self.data {
+                Data::Inline { len, buffer } => {
+                    if index >= *len {
+                        panic!("removal index (is {}) should be < len (is {})", index, len);
+                    }
+                    unsafe {
+                        let slot = (buffer.as_mut_ptr() as *mut A::Item).add(index);
+                        let value = slot.read();
+                        slot.add(1).copy_to(slot, *len - index - 1);
+                        *len -= 1;
+                        value
+                    }
+                }
+                Data::Heap(vec) => vec.remove(index),
+            }
+        }
+
+        // Inline elements past new_len are dropped in place, spilled ones by vec::Vec
+        pub fn truncate(&mut self, new_len: usize) {
+            match &mut self.data {
+                Data::Inline { len, buffer } => {
+                    if new_len < *len {
+                        let tail = ptr::slice_from_raw_parts_mut(
+                            unsafe { (buffer.as_mut_ptr() as *mut A::Item).add(new_len) },
+                            *len - new_len,
+                        );
+                        *len = new_len;
+                        unsafe { ptr::drop_in_place(tail) };
+                    }
+                }
+                Data::Heap(vec) => vec.truncate(new_len),
+            }
+        }
+
+        pub fn as_slice(&self) -> &[A::Item] {
+            match &self.data {
+                Data::Inline { len, buffer } => unsafe {
+                    slice::from_raw_parts(buffer.as_ptr() as *const A::Item, *len)
+                },
+                Data::Heap(vec) => vec.as_slice(),
+            }
+        }
+
+        pub fn as_mut_slice(&mut self) -> &mut [A::Item] {
+            match &mut self.data {
+                Data::Inline { len, buffer } => unsafe {
+                    slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut A::Item, *len)
+                },
+                Data::Heap(vec) => vec.as_mut_slice(),
+            }
+        }
+
+        // Moves inline elements to a heap buffer twice the inline capacity
+        fn spill_if_full(&mut self) {
+            if let Data::Inline { len, buffer } = &mut self.data {
+                if *len == A::CAPACITY {
+                    let len = mem::replace(len, 0);
+                    let mut vec = Vec::with_capacity(A::CAPACITY * 2);
+                    let elements = buffer.as_ptr() as *const A::Item;
+                    for i in 0..len {
+                        vec.push(unsafe { elements.add(i).read() });
+                    }
+                    self.data = Data::Heap(vec);
+                }
+            }
+        }
+    }
+
+    impl<A: Array> Default for SmallVec<A> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<A: Array> Drop for SmallVec<A> {
+        fn drop(&mut self) {
+            // Heap elements are dropped by vec::Vec
+            if let Data::Inline { len, buffer } = &mut self.data {
+                unsafe {
+                    ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                        buffer.as_mut_ptr() as *mut A::Item,
+                        *len,
+                    ));
+                }
+            }
+        }
+    }
+
+    impl<A: Array> Deref for SmallVec<A> {
+        type Target = [A::Item];
+
+        fn deref(&self) -> &Self::Target {
+            self.as_slice()
+        }
+    }
+
+    impl<A: Array> DerefMut for SmallVec<A> {
+        fn deref_mut(&mut self) -> &mut Self::Target {
+            self.as_mut_slice()
+        }
+    }
+
+    impl<A: Array> Clone for SmallVec<A>
+    where
+        A::Item: Clone,
+    {
+        fn clone(&self) -> Self {
+            self.iter().cloned().collect()
+        }
+    }
+
+    impl<A: Array> Debug for SmallVec<A>
+    where
+        A::Item: Debug,
+    {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            Debug::fmt(self.as_slice(), f)
+        }
+    }
+
+    impl<A: Array> PartialEq for SmallVec<A>
+    where
+        A::Item: PartialEq,
+    {
+        fn eq(&self, other: &Self) -> bool {
+            self.as_slice() == other.as_slice()
+        }
+    }
+
+    impl<A: Array> Eq for SmallVec<A> where A::Item: Eq {}
+
+    impl<'a, A: Array> IntoIterator for &'a SmallVec<A> {
+        type Item = &'a A::Item;
+        type IntoIter = slice::Iter<'a, A::Item>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.as_slice().iter()
+        }
+    }
+
+    impl<A: Array> FromIterator<A::Item> for SmallVec<A> {
+        fn from_iter<I: IntoIterator<Item = A::Item>>(iter: I) -> Self {
+            let mut result = SmallVec::new();
+            result.extend(iter);
+            result
+        }
+    }
+
+    impl<A: Array> Extend<A::Item> for SmallVec<A> {
+        fn extend<I: IntoIterator<Item = A::Item>>(&mut self, iter: I) {
+            for value in iter {
+                self.push(value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::small_vec::small_vec::SmallVec;
+    use crate::tracked::tracked::Tracked;
+    use std::cell::Cell;
+
+    #[test]
+    fn should_keep_values_inline() {
+        let mut vec: SmallVec<[i32; 4]> = SmallVec::new();
+
+        vec.extend([1, 2, 3, 4]);
+
+        assert_eq!(vec.spilled(), false);
+        assert_eq!(vec.capacity(), 4);
+        assert_eq!(&vec[..], &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn should_spill_when_full() {
+        let mut vec: SmallVec<[i32; 4]> = SmallVec::new();
+
+        vec.extend([1, 2, 3, 4, 5]);
+
+        assert_eq!(vec.spilled(), true);
+        assert_eq!(vec.capacity(), 8);
+        assert_eq!(&vec[..], &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn should_pop_inline_and_spilled() {
+        let mut inline: SmallVec<[i32; 4]> = [1, 2].into_iter().collect();
+        let mut spilled: SmallVec<[i32; 1]> = [1, 2].into_iter().collect();
+
+        assert_eq!(inline.pop(), Some(2));
+        assert_eq!(inline.pop(), Some(1));
+        assert_eq!(inline.pop(), None);
+        assert_eq!(spilled.pop(), Some(2));
+        assert_eq!(spilled.pop(), Some(1));
+        assert_eq!(spilled.pop(), None);
+    }
+
+    #[test]
+    fn should_insert_and_remove_inline() {
+        let mut vec: SmallVec<[i32; 4]> = [1, 3].into_iter().collect();
+
+        vec.insert(1, 2);
+        vec.insert(3, 4);
+
+        assert_eq!(&vec[..], &[1, 2, 3, 4]);
+        assert_eq!(vec.remove(0), 1);
+        assert_eq!(vec.remove(2), 4);
+        assert_eq!(&vec[..], &[2, 3]);
+        assert_eq!(vec.spilled(), false);
+    }
+
+    #[test]
+    fn should_spill_on_insert_into_full() {
+        let mut vec: SmallVec<[i32; 2]> = [1, 3].into_iter().collect();
+
+        vec.insert(1, 2);
+
+        assert_eq!(vec.spilled(), true);
+        assert_eq!(&vec[..], &[1, 2, 3]);
+        assert_eq!(vec.remove(1), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "removal index (is 2) should be < len (is 2)")]
+    fn should_panic_on_remove_past_end() {
+        let mut vec: SmallVec<[i32; 4]> = [1, 2].into_iter().collect();
+
+        vec.remove(2);
+    }
+
+    #[test]
+    fn should_act_as_slice() {
+        let mut vec: SmallVec<[i32; 4]> = [3, 1, 2].into_iter().collect();
+
+        vec.sort();
+        vec[0] = 5;
+
+        assert_eq!(vec.len(), 3);
+        assert_eq!(vec.contains(&5), true);
+        assert_eq!(vec.iter().sum::<i32>(), 10);
+        assert_eq!(format!("{:?}", vec), "[5, 2, 3]");
+    }
+
+    #[test]
+    fn should_spill_without_inline_capacity() {
+        let mut vec: SmallVec<[String; 0]> = SmallVec::new();
+
+        vec.push(String::from("a"));
+
+        assert_eq!(vec.spilled(), true);
+        assert_eq!(vec[0], "a");
+    }
+
+    #[test]
+    fn should_drop_each_element_once() {
+        let drops = Cell::new(0);
+        let mut inline: SmallVec<[Tracked; 4]> = SmallVec::new();
+        let mut spilled: SmallVec<[Tracked; 2]> = SmallVec::new();
+
+        for _ in 0..3 {
+            inline.push(Tracked(&drops));
+            spilled.push(Tracked(&drops));
+        }
+        inline.truncate(1);
+        assert_eq!(drops.get(), 2);
+        drop(inline);
+        drop(spilled);
+
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn should_clone_and_compare() {
+        let vec: SmallVec<[String; 2]> = ["a", "b", "c"].into_iter().map(String::from).collect();
+
+        let result = vec.clone();
+
+        assert_eq!(result, vec);
+    }
+}
@@ -0,0 +1,13 @@
+pub mod tracked {
+    use core::cell::Cell;
+
+    // Test element counting its own drops, shared by container unit tests
+    #[derive(Clone)]
+    pub struct Tracked<'a>(pub &'a Cell<usize>);
+
+    impl Drop for Tracked<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+}
@@ -0,0 +1,307 @@
+pub mod validated {
+    use crate::either::either::Either;
+    use crate::vec::vec::Vec;
+    use core::ops::Index;
+
+    // Vector holding at least one element
+    pub struct NonEmptyVec<T>(Vec<T>);
+
+    impl<T> NonEmptyVec<T> {
+        pub fn new(first: T) -> Self {
+            let mut vec = Vec::new();
+            vec.push(first);
+            NonEmptyVec(vec)
+        }
+
+        // Never empty, so there is no is_empty
+        #[allow(clippy::len_without_is_empty)]
+        pub fn len(&self) -> usize {
+            self.0.len()
+        }
+
+        pub fn first(&self) -> &T {
+            &self.0[0]
+        }
+
+        pub fn push(&mut self, value: T) {
+            self.0.push(value);
+        }
+
+        pub fn append(&mut self, mut other: NonEmptyVec<T>) {
+            self.0.append(&mut other.0);
+        }
+
+        pub fn iter(&self) -> impl Iterator<Item = &T> {
+            self.0.into_iter()
+        }
+
+        pub fn into_vec(self) -> Vec<T> {
+            self.0
+        }
+    }
+
+    impl<T> Index<usize> for NonEmptyVec<T> {
+        type Output = T;
+
+        fn index(&self, index: usize) -> &Self::Output {
+            &self.0[index]
+        }
+    }
+
+    // Unlike Either, combining Validated values keeps errors of all of them
+    pub enum Validated<E, T> {
+        Valid(T),
+        Invalid(NonEmptyVec<E>),
+    }
+
+    impl<E, T> Validated<E, T> {
+        pub fn valid(value: T) -> Self {
+            Validated::Valid(value)
+        }
+
+        pub fn invalid(error: E) -> Self {
+            Validated::Invalid(NonEmptyVec::new(error))
+        }
+
+        pub fn is_valid(&self) -> bool {
+            match self {
+                Validated::Valid(_) => true,
+                Validated::Invalid(_) => false,
+            }
+        }
+
+        pub fn is_invalid(&self) -> bool {
+            !self.is_valid()
+        }
+
+        pub fn errors(&self) -> Option<&NonEmptyVec<E>> {
+            match self {
+                Validated::Valid(_) => None,
+                Validated::Invalid(errors) => Some(errors),
+            }
+        }
+
+        pub fn map<F, U>(self, f: F) -> Validated<E, U>
+        where
+            F: FnOnce(T) -> U,
+        {
+            match self {
+                Validated::Valid(value) => Validated::Valid(f(value)),
+                Validated::Invalid(errors) => Validated::Invalid(errors),
+            }
+        }
+
+        pub fn zip<U>(self, other: Validated<E, U>) -> Validated<E, (T, U)> {
+            match (self, other) {
+                (Validated::Valid(first), Validated::Valid(second)) => {
+                    Validated::Valid((first, second))
+                }
+                (Validated::Invalid(mut errors), Validated::Invalid(other)) => {
+                    errors.append(other);
+                    Validated::Invalid(errors)
+                }
+                (Validated::Invalid(errors), Validated::Valid(_))
+                | (Validated::Valid(_), Validated::Invalid(errors)) => Validated::Invalid(errors),
+            }
+        }
+
+        pub fn zip3<U, V>(
+            self,
+            second: Validated<E, U>,
+            third: Validated<E, V>,
+        ) -> Validated<E, (T, U, V)> {
+            self.zip(second)
+                .zip(third)
+                .map(|((first, second), third)| (first, second, third))
+        }
+
+        // Keeps value of other, errors of both
+        pub fn and<U>(self, other: Validated<E, U>) -> Validated<E, U> {
+            self.zip(other).map(|(_, value)| value)
+        }
+
+        pub fn unwrap(self) -> T {
+            match self {
+                Validated::Valid(value) => value,
+                Validated::Invalid(_) => {
+                    panic!("Called Validated::unwrap() on Invalid value!")
+                }
+            }
+        }
+
+        pub fn into_either(self) -> Either<NonEmptyVec<E>, T> {
+            match self {
+                Validated::Valid(value) => Either::Right(value),
+                Validated::Invalid(errors) => Either::Left(errors),
+            }
+        }
+
+        // Used by validate! to gather errors of every field
+        #[doc(hidden)]
+        pub fn take_errors(self, errors: &mut Option<NonEmptyVec<E>>) -> Option<T> {
+            match (self, errors.as_mut()) {
+                (Validated::Valid(value), _) => Some(value),
+                (Validated::Invalid(new), Some(errors)) => {
+                    errors.append(new);
+                    None
+                }
+                (Validated::Invalid(new), None) => {
+                    *errors = Some(new);
+                    None
+                }
+            }
+        }
+    }
+
+    impl<E, T> From<Either<E, T>> for Validated<E, T> {
+        fn from(value: Either<E, T>) -> Self {
+            match value {
+                Either::Left(error) => Validated::invalid(error),
+                Either::Right(value) => Validated::Valid(value),
+            }
+        }
+    }
+
+    impl<E, T> From<Validated<E, T>> for Either<NonEmptyVec<E>, T> {
+        fn from(value: Validated<E, T>) -> Self {
+            value.into_either()
+        }
+    }
+
+    // Builds a value from several Validated fields, reporting errors of all invalid fields:
+    // validate! { name = check_name(input), age = check_age(input) => Person { name, age } }
+    #[macro_export]
+    macro_rules! validate {
+        ($($name:ident = $expr:expr),+ $(,)? => $result:expr) => {{
+            let mut errors = None;
+            $(
+                let $name = $crate::validated::validated::Validated::take_errors($expr, &mut errors);
+            )+
+            match errors {
+                Some(errors) => $crate::validated::validated::Validated::Invalid(errors),
+                None => {
+                    $(
+                        let $name = $name.unwrap();
+                    )+
+                    $crate::validated::validated::Validated::Valid($result)
+                }
+            }
+        }};
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::either::either::Either;
+    use crate::validate;
+    use crate::validated::validated::Validated;
+
+    struct Person {
+        name: String,
+        age: u32,
+    }
+
+    fn check_name(name: &str) -> Validated<String, String> {
+        if name.is_empty() {
+            Validated::invalid(String::from("Name is empty"))
+        } else {
+            Validated::valid(String::from(name))
+        }
+    }
+
+    fn check_age(age: u32) -> Validated<String, u32> {
+        if age < 18 {
+            Validated::invalid(format!("Age {} is below 18", age))
+        } else {
+            Validated::valid(age)
+        }
+    }
+
+    #[test]
+    fn should_combine_valid_values() {
+        let result = check_name("John").zip(check_age(20));
+
+        let (name, age) = result.unwrap();
+
+        assert_eq!(name, "John");
+        assert_eq!(age, 20);
+    }
+
+    #[test]
+    fn should_accumulate_errors() {
+        let result = check_name("").zip(check_age(10));
+
+        let errors = result.errors().unwrap();
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0], "Name is empty");
+        assert_eq!(errors[1], "Age 10 is below 18");
+    }
+
+    #[test]
+    fn should_accumulate_errors_of_three_values() {
+        let result = check_age(1).zip3(check_name("John"), check_age(2));
+
+        let errors: Vec<&String> = result.errors().unwrap().iter().collect();
+
+        assert_eq!(errors, ["Age 1 is below 18", "Age 2 is below 18"]);
+    }
+
+    #[test]
+    fn should_keep_errors_of_both_on_and() {
+        let valid = check_name("John").and(check_age(20));
+        let invalid = check_name("").and(check_age(20));
+
+        assert_eq!(valid.unwrap(), 20);
+        assert_eq!(invalid.errors().unwrap().first(), "Name is empty");
+    }
+
+    #[test]
+    fn should_build_value_with_macro() {
+        let result = validate! {
+            name = check_name("John"),
+            age = check_age(20) => Person { name, age }
+        };
+
+        let person = result.unwrap();
+
+        assert_eq!(person.name, "John");
+        assert_eq!(person.age, 20);
+    }
+
+    #[test]
+    fn should_report_all_errors_with_macro() {
+        let result = validate! {
+            name = check_name(""),
+            age = check_age(10) => Person { name, age }
+        };
+
+        assert_eq!(result.is_invalid(), true);
+        assert_eq!(result.errors().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn should_convert_from_either() {
+        let left: Either<String, u32> = Either::Left(String::from("Error"));
+        let right: Either<String, u32> = Either::Right(20);
+
+        let left = Validated::from(left);
+        let right = Validated::from(right);
+
+        assert_eq!(left.errors().unwrap().first(), "Error");
+        assert_eq!(right.unwrap(), 20);
+    }
+
+    #[test]
+    fn should_convert_into_either() {
+        let result: Either<_, String> = check_name("")
+            .zip(check_age(10))
+            .map(|_| String::new())
+            .into();
+
+        let errors = result.unwrap_left();
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors.into_vec()[1], "Age 10 is below 18");
+    }
+}
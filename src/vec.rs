@@ -1,24 +1,113 @@
 pub mod vec {
-    use std::alloc::{alloc, dealloc, Layout};
-    use std::marker::PhantomData;
-    use std::ops::{Index, IndexMut, Range};
-    use std::ptr::null_mut;
-    use std::slice;
+    use alloc::alloc::{alloc, dealloc, handle_alloc_error, Layout};
+    use core::marker::PhantomData;
+    use core::mem;
+    use core::ops::{Index, IndexMut, Range};
+    use core::ptr::{self, NonNull};
+    use core::slice;
 
     const INIT_SIZE: usize = 4;
 
+    // Allocation of growable containers, the owner tracks which elements are initialized
+    pub(crate) struct RawVec<T> {
+        // Dangling until the first allocation, zero-sized elements are never allocated
+        ptr: NonNull<T>,
+        allocated: usize,
+        // Owner drops elements, dropping it may drop T
+        phantom: PhantomData<T>,
+    }
+
+    impl<T> RawVec<T> {
+        const IS_ZST: bool = mem::size_of::<T>() == 0;
+
+        pub(crate) fn new() -> Self {
+            RawVec {
+                ptr: NonNull::dangling(),
+                allocated: if Self::IS_ZST { usize::MAX } else { 0 },
+                phantom: PhantomData,
+            }
+        }
+
+        pub(crate) fn with_capacity(capacity: usize) -> Self {
+            if capacity == 0 || Self::IS_ZST {
+                return Self::new();
+            }
+            RawVec {
+                ptr: unsafe { Self::alloc(capacity) },
+                allocated: capacity,
+                phantom: PhantomData,
+            }
+        }
+
+        pub(crate) fn ptr(&self) -> NonNull<T> {
+            self.ptr
+        }
+
+        pub(crate) fn allocated(&self) -> usize {
+            self.allocated
+        }
+
+        // Allocates INIT_SIZE first, then four times the previous size,
+        // first len elements are moved to the new allocation
+        pub(crate) fn grow(&mut self, len: usize) {
+            let new_size = if self.allocated == 0 {
+                INIT_SIZE
+            } else {
+                self.allocated.checked_mul(4).expect("capacity overflow")
+            };
+            unsafe {
+                let new_ptr = Self::alloc(new_size);
+                self.ptr.copy_to_nonoverlapping(new_ptr, len);
+                self.release();
+                self.ptr = new_ptr;
+            }
+            self.allocated = new_size;
+        }
+
+        unsafe fn alloc(capacity: usize) -> NonNull<T> {
+            let layout = Self::array_layout(capacity);
+            NonNull::new(alloc(layout) as *mut T).unwrap_or_else(|| handle_alloc_error(layout))
+        }
+
+        unsafe fn release(&mut self) {
+            if !Self::IS_ZST && self.allocated > 0 {
+                dealloc(
+                    self.ptr.as_ptr() as *mut u8,
+                    Self::array_layout(self.allocated),
+                );
+            }
+        }
+
+        fn array_layout(capacity: usize) -> Layout {
+            Layout::array::<T>(capacity).unwrap()
+        }
+    }
+
+    impl<T> Drop for RawVec<T> {
+        fn drop(&mut self) {
+            unsafe { self.release() }
+        }
+    }
+
+    /// ```compile_fail,E0277
+    /// fn assert_send<T: Send>() {}
+    /// assert_send::<rust_samples::vec::vec::Vec<rust_samples::rc::rc::Rc<i32>>>();
+    /// ```
     pub struct Vec<T> {
-        ptr: *mut T,
+        buffer: RawVec<T>,
         size: usize,
-        allocated: usize,
     }
 
+    // Vec is an exclusive owner, so thread safety is that of T
+    unsafe impl<T: Send> Send for Vec<T> {}
+
+    unsafe impl<T: Sync> Sync for Vec<T> {}
+
     impl<T> Vec<T> {
         pub fn new() -> Self {
             Vec {
-                ptr: null_mut(),
+                buffer: RawVec::new(),
                 size: 0,
-                allocated: 0,
             }
         }
 
@@ -26,41 +115,115 @@ pub mod vec {
             self.size
         }
 
+        pub fn is_empty(&self) -> bool {
+            self.size == 0
+        }
+
         pub fn allocated(&self) -> usize {
-            self.allocated
+            self.buffer.allocated()
+        }
+
+        pub fn as_ptr(&self) -> *const T {
+            self.buffer.ptr().as_ptr()
+        }
+
+        pub fn as_slice(&self) -> &[T] {
+            unsafe { slice::from_raw_parts(self.buffer.ptr().as_ptr(), self.size) }
+        }
+
+        pub fn as_mut_slice(&mut self) -> &mut [T] {
+            unsafe { slice::from_raw_parts_mut(self.buffer.ptr().as_ptr(), self.size) }
+        }
+
+        // Caller is responsible for elements between old and new length
+        pub(crate) unsafe fn set_len(&mut self, len: usize) {
+            self.size = len;
         }
 
         pub fn with_capacity(capacity: usize) -> Self {
+            Vec {
+                buffer: RawVec::with_capacity(capacity),
+                size: 0,
+            }
+        }
+
+        pub fn push(&mut self, value: T) {
+            self.grow_if_full();
+            unsafe { self.buffer.ptr().add(self.size).write(value) };
+            self.size += 1;
+        }
+
+        pub fn pop(&mut self) -> Option<T> {
+            if self.size == 0 {
+                None
+            } else {
+                self.size -= 1;
+                unsafe { Some(self.buffer.ptr().add(self.size).read()) }
+            }
+        }
+
+        // Shifts elements after index one slot to the right
+        pub fn insert(&mut self, index: usize, value: T) {
+            if index > self.size {
+                panic!(
+                    "insertion index (is {}) should be <= len (is {})",
+                    index, self.size
+                );
+            }
+            self.grow_if_full();
             unsafe {
-                let ptr = Self::alloc(capacity);
-                Vec {
-                    ptr,
-                    size: 0,
-                    allocated: capacity,
-                }
+                let slot = self.buffer.ptr().add(index);
+                slot.copy_to(slot.add(1), self.size - index);
+                slot.write(value);
             }
+            self.size += 1;
         }
 
-        unsafe fn alloc(capacity: usize) -> *mut T {
-            alloc(Self::array_layout(capacity)) as *mut T
+        // Shifts elements after index one slot to the left
+        pub fn remove(&mut self, index: usize) -> T {
+            if index >= self.size {
+                panic!(
+                    "removal index (is {}) should be < len (is {})",
+                    index, self.size
+                );
+            }
+            unsafe {
+                let slot = self.buffer.ptr().add(index);
+                let value = slot.read();
+                slot.add(1).copy_to(slot, self.size - index - 1);
+                self.size -= 1;
+                value
+            }
         }
 
-        pub fn push(&mut self, value: T) {
+        // Drops elements from len onwards, allocation is kept
+        pub fn truncate(&mut self, len: usize) {
+            if len < self.size {
+                let tail = ptr::slice_from_raw_parts_mut(
+                    unsafe { self.buffer.ptr().add(len).as_ptr() },
+                    self.size - len,
+                );
+                // Length is updated first, so a panicking drop can't drop the tail twice
+                self.size = len;
+                unsafe { ptr::drop_in_place(tail) };
+            }
+        }
+
+        // Makes room for one more element
+        fn grow_if_full(&mut self) {
+            if self.size == self.buffer.allocated() {
+                self.buffer.grow(self.size);
+            }
+        }
+
+        // Moves all elements of other to the end of self, leaving other empty
+        pub fn append(&mut self, other: &mut Vec<T>) {
+            // Other gives up its elements first, so a panicking push can't drop them twice
+            let len = mem::replace(&mut other.size, 0);
             unsafe {
-                let ptr = if self.allocated == 0 {
-                    self.allocated = INIT_SIZE;
-                    self.ptr = Self::alloc(INIT_SIZE);
-                    self.ptr
-                } else {
-                    self.ptr
-                };
-                if self.size == self.allocated {
-                    let new_size = self.allocated << 2;
-                    let new_ptr = Self::alloc(new_size);
-                    ptr.copy_to_nonoverlapping(new_ptr, self.size);
+                for i in 0..len {
+                    self.push(other.buffer.ptr().add(i).read());
                 }
-                *(ptr.add(self.size)) = value;
-                self.size += 1;
             }
         }
 
@@ -69,27 +232,39 @@ pub mod vec {
             T: Clone,
         {
             let mut vec: Vec<T> = Vec::with_capacity(count);
-            unsafe {
-                for i in 0..count {
-                    *vec.ptr.add(i) = elem.clone();
-                }
+            // Slots are uninitialized, length grows with each write so a panicking clone drops only written ones
+            for _ in 0..count {
+                unsafe { vec.buffer.ptr().add(vec.size).write(elem.clone()) };
+                vec.size += 1;
             }
-            vec.size = count;
             vec
         }
 
-        fn array_layout(capacity: usize) -> Layout {
-            Layout::array::<T>(capacity).unwrap()
+        fn check_index(&self, index: usize) {
+            if index >= self.size {
+                panic!(
+                    "index out of bounds: the len is {} but the index is {}",
+                    self.size, index
+                );
+            }
+        }
+    }
+
+    impl<T> Default for Vec<T> {
+        fn default() -> Self {
+            Self::new()
         }
     }
 
     impl<T> Drop for Vec<T> {
         fn drop(&mut self) {
             unsafe {
-                if self.allocated > 0 {
-                    dealloc(self.ptr as *mut u8, Self::array_layout(self.allocated));
-                }
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                    self.buffer.ptr().as_ptr(),
+                    self.size,
+                ));
             }
+            // Allocation is released by RawVec
         }
     }
 
@@ -97,7 +272,8 @@ pub mod vec {
         type Output = T;
 
         fn index(&self, index: usize) -> &Self::Output {
-            unsafe { &*self.ptr.add(index) }
+            self.check_index(index);
+            unsafe { self.buffer.ptr().add(index).as_ref() }
         }
     }
 
@@ -105,29 +281,46 @@ pub mod vec {
         type Output = [T];
 
         fn index(&self, index: Range<usize>) -> &Self::Output {
-            unsafe { slice::from_raw_parts(self.ptr.add(index.start), index.end - index.start) }
+            if index.start > index.end || index.end > self.size {
+                panic!(
+                    "range {}..{} out of bounds for length {}",
+                    index.start, index.end, self.size
+                );
+            }
+            unsafe {
+                slice::from_raw_parts(
+                    self.buffer.ptr().add(index.start).as_ptr(),
+                    index.end - index.start,
+                )
+            }
         }
     }
 
     impl<T> IndexMut<usize> for Vec<T> {
         fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-            unsafe { &mut *self.ptr.add(index) }
+            self.check_index(index);
+            unsafe { self.buffer.ptr().add(index).as_mut() }
         }
     }
 
     pub struct VecIterator<'a, T> {
-        ptr: *mut T,
+        ptr: NonNull<T>,
         offset: usize,
         size: usize,
-        phantom: PhantomData<&'a ()>,
+        phantom: PhantomData<&'a T>,
     }
 
+    // Iterator only hands out shared references, like &[T]
+    unsafe impl<T: Sync> Send for VecIterator<'_, T> {}
+
+    unsafe impl<T: Sync> Sync for VecIterator<'_, T> {}
+
     impl<'a, T: 'a> Iterator for VecIterator<'a, T> {
         type Item = &'a T;
 
         fn next(&mut self) -> Option<Self::Item> {
             if self.offset < self.size {
-                let result = unsafe { Option::from(&*self.ptr.add(self.offset)) };
+                let result = unsafe { Option::from(self.ptr.add(self.offset).as_ref()) };
                 self.offset += 1;
                 result
             } else {
@@ -142,10 +335,10 @@ pub mod vec {
 
         fn into_iter(self) -> Self::IntoIter {
             VecIterator {
-                ptr: self.ptr,
+                ptr: self.buffer.ptr(),
                 offset: 0,
                 size: self.size,
-                phantom: PhantomData::default(),
+                phantom: PhantomData,
             }
         }
     }
@@ -153,14 +346,78 @@ pub mod vec {
     impl<T> FromIterator<T> for Vec<T> {
         fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
             let mut result = Vec::new();
-            let mut iterator = iter.into_iter();
-            while let Some(value) = iterator.next() {
+            for value in iter {
                 result.push(value);
             }
             result
         }
     }
 
+    impl<T> Extend<T> for Vec<T> {
+        fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+            for value in iter {
+                self.push(value);
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<T: serde::Serialize> serde::Serialize for Vec<T> {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            use serde::ser::SerializeSeq;
+
+            let mut seq = serializer.serialize_seq(Some(self.len()))?;
+            for value in self {
+                seq.serialize_element(value)?;
+            }
+            seq.end()
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Vec<T> {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            // Length hint comes from the input, so preallocation is capped
+            const MAX_PREALLOCATED: usize = 4096;
+
+            struct VecVisitor<T>(PhantomData<T>);
+
+            impl<'de, T: serde::Deserialize<'de>> serde::de::Visitor<'de> for VecVisitor<T> {
+                type Value = Vec<T>;
+
+                fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    f.write_str("a sequence")
+                }
+
+                fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                    self,
+                    mut seq: A,
+                ) -> Result<Self::Value, A::Error> {
+                    let capacity = seq.size_hint().unwrap_or(0).min(MAX_PREALLOCATED);
+                    let mut vec = Vec::with_capacity(capacity);
+                    while let Some(value) = seq.next_element()? {
+                        vec.push(value);
+                    }
+                    Ok(vec)
+                }
+            }
+
+            deserializer.deserialize_seq(VecVisitor(PhantomData))
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::io::Write for Vec<u8> {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.extend(buf.iter().copied());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
     #[macro_export]
     macro_rules! vec {
         () => { Vec::new() };
@@ -169,7 +426,7 @@ pub mod vec {
         };
         ( $($x:expr),* ) => {
             {
-                use crate::count_tts;
+                use $crate::count_tts;
                 let mut temp = Vec::with_capacity(count_tts!($($x)*));
                 $(
                     temp.push($x);
@@ -179,6 +436,7 @@ pub mod vec {
         };
     }
 
+    #[allow(unused_imports)]
     pub(crate) use crate::vec;
 }
 
@@ -215,6 +473,112 @@ mod tests {
         assert_eq!(vec[1], 17);
     }
 
+    #[test]
+    fn should_keep_values_after_growth() {
+        let mut vec: Vec<i32> = Vec::new();
+
+        for value in 0..20 {
+            vec.push(value);
+        }
+
+        assert_eq!(vec.len(), 20);
+        assert_eq!(vec.allocated(), 64);
+        assert_eq!(vec[0], 0);
+        assert_eq!(vec[19], 19);
+    }
+
+    #[test]
+    fn should_extend_with_values() {
+        let mut vec: Vec<i32> = vec![1];
+
+        vec.extend([2, 3]);
+
+        assert_eq!(vec.len(), 3);
+        assert_eq!(vec[2], 3);
+    }
+
+    #[test]
+    fn should_move_values_on_append() {
+        let mut first: Vec<i32> = vec![1, 2];
+        let mut second: Vec<i32> = vec![3, 4, 5];
+
+        first.append(&mut second);
+
+        assert_eq!(first.len(), 5);
+        assert_eq!(first[4], 5);
+        assert_eq!(second.len(), 0);
+    }
+
+    #[test]
+    fn should_pop_last_value() {
+        let mut vec: Vec<i32> = vec![1, 2];
+
+        assert_eq!(vec.pop(), Some(2));
+        assert_eq!(vec.pop(), Some(1));
+        assert_eq!(vec.pop(), None);
+        assert_eq!(vec.len(), 0);
+    }
+
+    #[test]
+    fn should_insert_value_at_index() {
+        let mut vec: Vec<i32> = vec![1, 2, 3, 4];
+
+        vec.insert(1, 5);
+        vec.insert(5, 6);
+
+        assert_eq!(vec.len(), 6);
+        assert_eq!(vec.allocated(), 16);
+        assert_eq!(&vec[0..6], &[1, 5, 2, 3, 4, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "insertion index (is 2) should be <= len (is 1)")]
+    fn should_panic_on_insert_past_end() {
+        let mut vec: Vec<i32> = vec![1];
+
+        vec.insert(2, 5);
+    }
+
+    #[test]
+    fn should_remove_value_at_index() {
+        let mut vec: Vec<i32> = vec![1, 2, 3];
+
+        assert_eq!(vec.remove(1), 2);
+        assert_eq!(vec.remove(1), 3);
+        assert_eq!(vec.len(), 1);
+        assert_eq!(vec[0], 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "removal index (is 1) should be < len (is 1)")]
+    fn should_panic_on_remove_past_end() {
+        let mut vec: Vec<i32> = vec![1];
+
+        vec.remove(1);
+    }
+
+    #[test]
+    fn should_truncate_keeping_allocation() {
+        let mut vec: Vec<String> = vec![String::from("a"), String::from("b"), String::from("c")];
+
+        vec.truncate(5);
+        vec.truncate(1);
+
+        assert_eq!(vec.len(), 1);
+        assert_eq!(vec.allocated(), 3);
+        assert_eq!(vec[0], "a");
+    }
+
+    #[test]
+    fn should_view_as_slice() {
+        let mut vec: Vec<i32> = vec![3, 1, 2];
+
+        vec.as_mut_slice().sort();
+
+        assert_eq!(vec.as_slice(), &[1, 2, 3]);
+        assert_eq!(Vec::<i32>::new().as_slice().len(), 0);
+    }
+
     #[test]
     fn should_construct_vector() {
         let vec: Vec<i32> = vec![1, 2, 3];
@@ -270,4 +634,74 @@ mod tests {
         assert_eq!(result[0], 2);
         assert_eq!(result[1], 4);
     }
+
+    #[test]
+    fn should_not_allocate_with_zero_capacity() {
+        let mut vec: Vec<i32> = Vec::with_capacity(0);
+
+        vec.push(1);
+
+        assert_eq!(vec.allocated(), 4);
+        assert_eq!(vec[0], 1);
+    }
+
+    #[test]
+    fn should_move_to_another_thread() {
+        let vec: Vec<i32> = vec![1, 2, 3];
+
+        let result = std::thread::spawn(move || vec.into_iter().sum::<i32>())
+            .join()
+            .unwrap();
+
+        assert_eq!(result, 6);
+    }
+
+    #[test]
+    fn should_share_between_threads() {
+        let vec: Vec<i32> = vec![1, 2, 3];
+
+        let result = std::thread::scope(|scope| {
+            let first = scope.spawn(|| vec[0]);
+            let last = scope.spawn(|| vec[2]);
+            first.join().unwrap() + last.join().unwrap()
+        });
+
+        assert_eq!(result, 4);
+    }
+
+    #[test]
+    fn should_be_covariant() {
+        fn shorten<'a>(vec: Vec<&'static str>) -> Vec<&'a str> {
+            vec
+        }
+
+        let vec = shorten(vec!["a"]);
+
+        assert_eq!(vec[0], "a");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_round_trip_through_json() {
+        let vec: Vec<i32> = vec![1, 2, 3, 4, 5];
+
+        let json = serde_json::to_string(&vec).unwrap();
+        let result: Vec<i32> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(json, "[1,2,3,4,5]");
+        assert_eq!(result.len(), 5);
+        assert_eq!(result[4], 5);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn should_write_bytes() {
+        use std::io::Write;
+
+        let mut vec: Vec<u8> = Vec::new();
+
+        write!(vec, "{}-{}", 1, 2).unwrap();
+
+        assert_eq!(&vec[0..3], b"1-2");
+    }
 }
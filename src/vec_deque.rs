@@ -0,0 +1,640 @@
+pub mod vec_deque {
+    use crate::vec::vec::RawVec;
+    use core::fmt::{Debug, Formatter};
+    use core::iter::FusedIterator;
+    use core::ops::{Index, IndexMut};
+    use core::ptr;
+    use core::slice;
+
+    // Ring buffer, elements start at head and wrap around the end of the allocation
+    pub struct VecDeque<T> {
+        buffer: RawVec<T>,
+        head: usize,
+        len: usize,
+    }
+
+    // Ring owns its elements outright, Send and Sync follow T
+    unsafe impl<T: Send> Send for VecDeque<T> {}
+
+    unsafe impl<T: Sync> Sync for VecDeque<T> {}
+
+    impl<T> VecDeque<T> {
+        pub fn new() -> Self {
+            VecDeque {
+                buffer: RawVec::new(),
+                head: 0,
+                len: 0,
+            }
+        }
+
+        pub fn with_capacity(capacity: usize) -> Self {
+            VecDeque {
+                buffer: RawVec::with_capacity(capacity),
+                head: 0,
+                len: 0,
+            }
+        }
+
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        pub fn allocated(&self) -> usize {
+            self.buffer.allocated()
+        }
+
+        pub fn push_back(&mut self, value: T) {
+            self.grow_if_full();
+            unsafe { self.ptr().add(self.slot(self.len)).write(value) };
+            self.len += 1;
+        }
+
+        pub fn push_front(&mut self, value: T) {
+            self.grow_if_full();
+            self.head = self.slot(self.allocated() - 1);
+            unsafe { self.ptr().add(self.head).write(value) };
+            self.len += 1;
+        }
+
+        pub fn pop_front(&mut self) -> Option<T> {
+            if self.len == 0 {
+                return None;
+            }
+            let value = unsafe { self.ptr().add(self.head).read() };
+            self.head = self.slot(1);
+            self.len -= 1;
+            Some(value)
+        }
+
+        pub fn pop_back(&mut self) -> Option<T> {
+            if self.len == 0 {
+                return None;
+            }
+            self.len -= 1;
+            unsafe { Some(self.ptr().add(self.slot(self.len)).read()) }
+        }
+
+        pub fn front(&self) -> Option<&T> {
+            self.get(0)
+        }
+
+        pub fn back(&self) -> Option<&T> {
+            self.len.checked_sub(1).and_then(|index| self.get(index))
+        }
+
+        pub fn get(&self, index: usize) -> Option<&T> {
+            if index < self.len {
+                unsafe { Some(&*self.ptr().add(self.slot(index))) }
+            } else {
+                None
+            }
+        }
+
+        pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+            if index < self.len {
+                unsafe { Some(&mut *self.ptr().add(self.slot(index))) }
+            } else {
+                None
+            }
+        }
+
+        // Pops from the back until len elements remain, head stays where it is
+        pub fn truncate(&mut self, len: usize) {
+            while self.len > len {
+                drop(self.pop_back());
+            }
+        }
+
+        pub fn clear(&mut self) {
+            self.truncate(0);
+        }
+
+        // Front slice starts at head, back slice holds elements wrapped to the start of the allocation
+        pub fn as_slices(&self) -> (&[T], &[T]) {
+            let (front, back) = self.ranges();
+            unsafe {
+                (
+                    slice::from_raw_parts(self.ptr().add(front.0), front.1),
+                    slice::from_raw_parts(self.ptr(), back),
+                )
+            }
+        }
+
+        pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+            let (front, back) = self.ranges();
+            unsafe {
+                (
+                    slice::from_raw_parts_mut(self.ptr().add(front.0), front.1),
+                    slice::from_raw_parts_mut(self.ptr(), back),
+                )
+            }
+        }
+
+        // Moves elements so that they are in one slice, order is kept.
+        // Only the len elements are moved, free slots are never touched.
+        pub fn make_contiguous(&mut self) -> &mut [T] {
+            let ((head, front), back) = self.ranges();
+            if back > 0 {
+                let free = self.allocated() - self.len;
+                let ptr = self.ptr();
+                unsafe {
+                    if free >= front {
+                        // Back part moves right, front part is copied before it at the start
+                        ptr.copy_to(ptr.add(front), back);
+                        ptr.add(head).copy_to_nonoverlapping(ptr, front);
+                        self.head = 0;
+                    } else if free >= back {
+                        // Front part moves left, back part is copied after it at the end
+                        ptr.add(head).copy_to(ptr.add(free), front);
+                        ptr.copy_to_nonoverlapping(ptr.add(free + front), back);
+                        self.head = free;
+                    } else if back < front {
+                        // Not enough room for either part, shorter one is moved next to
+                        // the other and the joined elements are rotated into order
+                        ptr.copy_to(ptr.add(free), back);
+                        slice::from_raw_parts_mut(ptr.add(free), self.len).rotate_left(back);
+                        self.head = free;
+                    } else {
+                        ptr.add(head).copy_to(ptr.add(back), front);
+                        slice::from_raw_parts_mut(ptr, self.len).rotate_right(front);
+                        self.head = 0;
+                    }
+                }
+            }
+            self.as_mut_slices().0
+        }
+
+        // Moves first n elements to the back
+        pub fn rotate_left(&mut self, n: usize) {
+            assert!(
+                n <= self.len,
+                "rotation {} out of bounds for length {}",
+                n,
+                self.len
+            );
+            // Moving the shorter side around the ring gives the same order
+            if n <= self.len - n {
+                for _ in 0..n {
+                    let value = self.pop_front().unwrap();
+                    self.push_back(value);
+                }
+            } else {
+                self.rotate_right(self.len - n);
+            }
+        }
+
+        // Moves last n elements to the front
+        pub fn rotate_right(&mut self, n: usize) {
+            assert!(
+                n <= self.len,
+                "rotation {} out of bounds for length {}",
+                n,
+                self.len
+            );
+            if n <= self.len - n {
+                for _ in 0..n {
+                    let value = self.pop_back().unwrap();
+                    self.push_front(value);
+                }
+            } else {
+                self.rotate_left(self.len - n);
+            }
+        }
+
+        pub fn iter(&self) -> Iter<'_, T> {
+            let (front, back) = self.as_slices();
+            Iter {
+                front: front.iter(),
+                back: back.iter(),
+            }
+        }
+
+        pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+            let (front, back) = self.as_mut_slices();
+            IterMut {
+                front: front.iter_mut(),
+                back: back.iter_mut(),
+            }
+        }
+
+        fn ptr(&self) -> *mut T {
+            self.buffer.ptr().as_ptr()
+        }
+
+        // Allocation slot of element at index, wrapping around the end
+        fn slot(&self, index: usize) -> usize {
+            let to_end = self.allocated() - self.head;
+            if index < to_end {
+                self.head + index
+            } else {
+                index - to_end
+            }
+        }
+
+        // ((start, len) of front part, len of wrapped part starting at slot 0)
+        fn ranges(&self) -> ((usize, usize), usize) {
+            let to_end = self.allocated() - self.head;
+            if self.len <= to_end {
+                ((self.head, self.len), 0)
+            } else {
+                ((self.head, to_end), self.len - to_end)
+            }
+        }
+
+        fn grow_if_full(&mut self) {
+            let allocated = self.allocated();
+            if self.len == allocated {
+                // Full ring is initialized slot by slot, so every slot is moved
+                self.buffer.grow(allocated);
+                // Wrapped elements go right after the old end, new allocation is at least twice as big
+                unsafe {
+                    self.ptr()
+                        .copy_to_nonoverlapping(self.ptr().add(allocated), self.head);
+                }
+            }
+        }
+
+        fn check_index(&self, index: usize) {
+            if index >= self.len {
+                panic!(
+                    "index out of bounds: the len is {} but the index is {}",
+                    self.len, index
+                );
+            }
+        }
+    }
+
+    impl<T> Default for VecDeque<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<T> Drop for VecDeque<T> {
+        fn drop(&mut self) {
+            let (front, back) = self.as_mut_slices();
+            let (front, back) = (front as *mut [T], back as *mut [T]);
+            unsafe {
+                ptr::drop_in_place(front);
+                ptr::drop_in_place(back);
+            }
+            // Ring slots are freed when the buffer field is dropped after this
+        }
+    }
+
+    impl<T> Index<usize> for VecDeque<T> {
+        type Output = T;
+
+        fn index(&self, index: usize) -> &Self::Output {
+            self.check_index(index);
+            self.get(index).unwrap()
+        }
+    }
+
+    impl<T> IndexMut<usize> for VecDeque<T> {
+        fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+            self.check_index(index);
+            self.get_mut(index).unwrap()
+        }
+    }
+
+    impl<T: Clone> Clone for VecDeque<T> {
+        fn clone(&self) -> Self {
+            self.iter().cloned().collect()
+        }
+    }
+
+    impl<T: Debug> Debug for VecDeque<T> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+
+    impl<T: PartialEq> PartialEq for VecDeque<T> {
+        fn eq(&self, other: &Self) -> bool {
+            self.len == other.len && self.iter().eq(other.iter())
+        }
+    }
+
+    impl<T: Eq> Eq for VecDeque<T> {}
+
+    impl<T> FromIterator<T> for VecDeque<T> {
+        fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+            let mut result = VecDeque::new();
+            result.extend(iter);
+            result
+        }
+    }
+
+    impl<T> Extend<T> for VecDeque<T> {
+        fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+            for value in iter {
+                self.push_back(value);
+            }
+        }
+    }
+
+    pub struct Iter<'a, T> {
+        front: slice::Iter<'a, T>,
+        back: slice::Iter<'a, T>,
+    }
+
+    impl<'a, T> Iterator for Iter<'a, T> {
+        type Item = &'a T;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.front.next().or_else(|| self.back.next())
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let len = self.front.len() + self.back.len();
+            (len, Some(len))
+        }
+    }
+
+    impl<T> DoubleEndedIterator for Iter<'_, T> {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            self.back.next_back().or_else(|| self.front.next_back())
+        }
+    }
+
+    impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+    impl<T> FusedIterator for Iter<'_, T> {}
+
+    pub struct IterMut<'a, T> {
+        front: slice::IterMut<'a, T>,
+        back: slice::IterMut<'a, T>,
+    }
+
+    impl<'a, T> Iterator for IterMut<'a, T> {
+        type Item = &'a mut T;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.front.next().or_else(|| self.back.next())
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let len = self.front.len() + self.back.len();
+            (len, Some(len))
+        }
+    }
+
+    impl<T> DoubleEndedIterator for IterMut<'_, T> {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            self.back.next_back().or_else(|| self.front.next_back())
+        }
+    }
+
+    impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+    impl<T> FusedIterator for IterMut<'_, T> {}
+
+    pub struct IntoIter<T>(VecDeque<T>);
+
+    impl<T> Iterator for IntoIter<T> {
+        type Item = T;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.pop_front()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.0.len, Some(self.0.len))
+        }
+    }
+
+    impl<T> DoubleEndedIterator for IntoIter<T> {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            self.0.pop_back()
+        }
+    }
+
+    impl<T> ExactSizeIterator for IntoIter<T> {}
+
+    impl<T> FusedIterator for IntoIter<T> {}
+
+    impl<T> IntoIterator for VecDeque<T> {
+        type Item = T;
+        type IntoIter = IntoIter<T>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            IntoIter(self)
+        }
+    }
+
+    impl<'a, T> IntoIterator for &'a VecDeque<T> {
+        type Item = &'a T;
+        type IntoIter = Iter<'a, T>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.iter()
+        }
+    }
+
+    impl<'a, T> IntoIterator for &'a mut VecDeque<T> {
+        type Item = &'a mut T;
+        type IntoIter = IterMut<'a, T>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.iter_mut()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tracked::tracked::Tracked;
+    use crate::vec_deque::vec_deque::VecDeque;
+    use std::cell::Cell;
+
+    // Ring of capacity 4 with elements wrapped around the end
+    fn wrapped() -> VecDeque<i32> {
+        let mut deque = VecDeque::with_capacity(4);
+        deque.push_back(2);
+        deque.push_back(3);
+        deque.push_front(1);
+        deque.push_front(0);
+        deque
+    }
+
+    #[test]
+    fn should_push_and_pop_at_both_ends() {
+        let mut deque: VecDeque<i32> = VecDeque::new();
+
+        deque.push_back(2);
+        deque.push_front(1);
+        deque.push_back(3);
+
+        assert_eq!(deque.len(), 3);
+        assert_eq!(deque.front(), Some(&1));
+        assert_eq!(deque.back(), Some(&3));
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_back(), Some(3));
+        assert_eq!(deque.pop_back(), Some(2));
+        assert_eq!(deque.pop_front(), None);
+        assert_eq!(deque.back(), None);
+    }
+
+    #[test]
+    fn should_index_across_wrap() {
+        let mut deque = wrapped();
+
+        deque[3] = 7;
+
+        assert_eq!(deque.allocated(), 4);
+        assert_eq!(deque[0], 0);
+        assert_eq!(deque[1], 1);
+        assert_eq!(deque[3], 7);
+        assert_eq!(deque.get(4), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 4 but the index is 4")]
+    fn should_panic_on_out_of_bounds_index() {
+        let deque = wrapped();
+
+        let _ = deque[4];
+    }
+
+    #[test]
+    fn should_split_wrapped_elements_into_slices() {
+        let deque = wrapped();
+
+        let (front, back) = deque.as_slices();
+
+        assert_eq!(front, &[0, 1]);
+        assert_eq!(back, &[2, 3]);
+    }
+
+    #[test]
+    fn should_keep_order_after_growth() {
+        let mut deque = wrapped();
+
+        deque.push_front(-1);
+        deque.push_back(4);
+
+        assert_eq!(deque.allocated(), 16);
+        assert_eq!(
+            deque.iter().copied().collect::<Vec<_>>(),
+            [-1, 0, 1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn should_make_contiguous() {
+        let mut deque = wrapped();
+
+        let slice = deque.make_contiguous();
+        slice.reverse();
+
+        assert_eq!(deque.as_slices(), (&[3, 2, 1, 0][..], &[][..]));
+        assert_eq!(deque.pop_front(), Some(3));
+    }
+
+    #[test]
+    fn should_make_contiguous_for_every_split() {
+        for len in 1..=8 {
+            for head in 0..8 {
+                let mut deque = VecDeque::with_capacity(8);
+                for _ in 0..head {
+                    deque.push_back(String::new());
+                    deque.pop_front();
+                }
+                for value in 0..len {
+                    deque.push_back(value.to_string());
+                }
+
+                let result = deque.make_contiguous().to_vec();
+
+                let expected: Vec<String> = (0..len).map(|x| x.to_string()).collect();
+                assert_eq!(result, expected);
+                assert_eq!(deque.as_slices().1.len(), 0);
+                assert_eq!(deque.allocated(), 8);
+            }
+        }
+    }
+
+    #[test]
+    fn should_rotate_both_ways() {
+        let mut deque: VecDeque<i32> = (0..5).collect();
+
+        deque.rotate_left(2);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [2, 3, 4, 0, 1]);
+        deque.rotate_left(4);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4, 0]);
+        deque.rotate_right(1);
+
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "rotation 3 out of bounds for length 2")]
+    fn should_panic_on_rotation_past_len() {
+        let mut deque: VecDeque<i32> = (0..2).collect();
+
+        deque.rotate_right(3);
+    }
+
+    #[test]
+    fn should_iterate_from_both_ends() {
+        let mut deque = wrapped();
+
+        for value in &mut deque {
+            *value *= 10;
+        }
+        let mut iter = deque.iter();
+
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next_back(), Some(&30));
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.rev().copied().collect::<Vec<_>>(), [20, 10]);
+        assert_eq!(deque.into_iter().rev().collect::<Vec<_>>(), [30, 20, 10, 0]);
+    }
+
+    #[test]
+    fn should_drop_each_element_once() {
+        let drops = Cell::new(0);
+        let mut deque = VecDeque::with_capacity(4);
+
+        for _ in 0..3 {
+            deque.push_front(Tracked(&drops));
+            deque.push_back(Tracked(&drops));
+        }
+        drop(deque.pop_front());
+        deque.truncate(3);
+        assert_eq!(drops.get(), 3);
+        drop(deque);
+
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn should_hold_zero_sized_elements() {
+        let mut deque: VecDeque<()> = VecDeque::new();
+
+        for _ in 0..100 {
+            deque.push_front(());
+            deque.push_back(());
+        }
+        deque.make_contiguous();
+
+        assert_eq!(deque.len(), 200);
+        assert_eq!(deque.iter().count(), 200);
+        assert_eq!(deque.pop_front(), Some(()));
+    }
+
+    #[test]
+    fn should_clone_and_compare() {
+        let deque: VecDeque<String> = ["a", "b"].into_iter().map(String::from).collect();
+
+        let result = deque.clone();
+
+        assert_eq!(result, deque);
+        assert_eq!(format!("{:?}", result), "[\"a\", \"b\"]");
+    }
+}
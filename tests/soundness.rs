@@ -0,0 +1,245 @@
+// Regression tests for paths that used to be undefined behaviour, run them with `cargo miri test`
+#![allow(clippy::bool_assert_comparison)]
+use rust_samples::boxed::boxed::Box;
+use rust_samples::rc::rc::{Rc, Weak};
+use rust_samples::vec::vec::Vec;
+use std::cell::Cell;
+use std::fmt::Display;
+
+#[derive(Clone)]
+struct Tracked<'a>(&'a Cell<usize>);
+
+impl Drop for Tracked<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn should_keep_values_after_growth() {
+    let mut vec: Vec<String> = Vec::new();
+
+    for value in 0..100 {
+        vec.push(value.to_string());
+    }
+
+    assert_eq!(vec.len(), 100);
+    assert_eq!(vec[0], "0");
+    assert_eq!(vec[99], "99");
+}
+
+#[test]
+fn should_drop_each_element_once() {
+    let drops = Cell::new(0);
+    let mut vec = Vec::new();
+
+    for _ in 0..20 {
+        vec.push(Tracked(&drops));
+    }
+    assert_eq!(drops.get(), 0);
+    drop(vec);
+
+    assert_eq!(drops.get(), 20);
+}
+
+#[test]
+fn should_drop_cloned_elements_once() {
+    let drops = Cell::new(0);
+
+    let vec = Vec::from_elem(Tracked(&drops), 5);
+    assert_eq!(drops.get(), 1);
+    drop(vec);
+
+    assert_eq!(drops.get(), 6);
+}
+
+#[test]
+fn should_drop_appended_elements_once() {
+    let drops = Cell::new(0);
+    let mut first = Vec::from_elem(Tracked(&drops), 3);
+    let mut second = Vec::from_elem(Tracked(&drops), 7);
+    drops.set(0);
+
+    first.append(&mut second);
+    drop(second);
+    assert_eq!(drops.get(), 0);
+    drop(first);
+
+    assert_eq!(drops.get(), 10);
+}
+
+#[test]
+fn should_hold_zero_sized_elements() {
+    let drops = Cell::new(0);
+    let mut units: Vec<()> = Vec::with_capacity(10);
+    let mut tracked = Vec::new();
+
+    for _ in 0..1000 {
+        units.push(());
+    }
+    for _ in 0..10 {
+        tracked.push(Tracked(&drops));
+    }
+
+    assert_eq!(units.len(), 1000);
+    assert_eq!(units[999], ());
+    assert_eq!(units.into_iter().count(), 1000);
+    drop(tracked);
+    assert_eq!(drops.get(), 10);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+fn should_panic_on_out_of_bounds_index() {
+    let mut vec = Vec::new();
+    vec.extend([1, 2, 3]);
+
+    let _ = vec[3];
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 0 but the index is 0")]
+fn should_panic_on_out_of_bounds_index_of_empty() {
+    let mut vec: Vec<i32> = Vec::new();
+
+    vec[0] = 1;
+}
+
+#[test]
+#[should_panic(expected = "range 2..5 out of bounds for length 3")]
+fn should_panic_on_out_of_bounds_range() {
+    let mut vec = Vec::new();
+    vec.extend([1, 2, 3]);
+
+    let _ = &vec[2..5];
+}
+
+#[test]
+fn should_return_empty_range_of_empty() {
+    let vec: Vec<i32> = Vec::new();
+
+    assert_eq!(vec[0..0].len(), 0);
+}
+
+#[test]
+fn should_box_zero_sized_value() {
+    let uat = Box::new(());
+
+    assert_eq!(*uat, ());
+    assert_eq!(Box::into_inner(uat), ());
+}
+
+#[test]
+fn should_drop_boxed_value_once() {
+    let drops = Cell::new(0);
+
+    drop(Box::new(Tracked(&drops)));
+    let value = Box::into_inner(Box::new(Tracked(&drops)));
+    assert_eq!(drops.get(), 1);
+    drop(value);
+
+    assert_eq!(drops.get(), 2);
+}
+
+#[test]
+fn should_count_nothing_for_new_weak() {
+    let weak: Weak<i32> = Weak::new();
+    let other = weak.clone();
+
+    assert_eq!(weak.strong_count(), 0);
+    assert_eq!(weak.weak_count(), 0);
+    assert_eq!(weak.upgrade(), None);
+    assert_eq!(weak.as_ptr().is_null(), true);
+    assert_eq!(weak.ptr_eq(&other), true);
+}
+
+#[test]
+fn should_round_trip_new_weak_through_raw() {
+    let weak: Weak<i32> = Weak::new();
+
+    let result = unsafe { Weak::from_raw(weak.into_raw()) };
+
+    assert_eq!(result.weak_count(), 0);
+}
+
+#[test]
+fn should_keep_allocation_for_weak_outliving_value() {
+    let drops = Cell::new(0);
+    let rc = Rc::new(Tracked(&drops));
+    let weak = Rc::downgrade(&rc);
+
+    drop(rc);
+
+    assert_eq!(drops.get(), 1);
+    assert_eq!(weak.strong_count(), 0);
+    assert_eq!(weak.weak_count(), 1);
+    assert_eq!(weak.upgrade().is_none(), true);
+}
+
+#[test]
+fn should_not_upgrade_during_new_cyclic() {
+    let rc = Rc::new_cyclic(|weak: &Weak<i32>| {
+        assert_eq!(weak.upgrade(), None);
+        assert_eq!(weak.strong_count(), 0);
+        5
+    });
+
+    assert_eq!(*rc, 5);
+    assert_eq!(Rc::weak_count(&rc), 0);
+}
+
+#[test]
+fn should_hold_empty_slice_and_str() {
+    let slice: Rc<[String]> = Rc::from(Vec::new());
+    let str: Rc<str> = Rc::from("");
+
+    assert_eq!(slice.len(), 0);
+    assert_eq!(&*str, "");
+}
+
+#[test]
+fn should_drop_slice_elements_once() {
+    let drops = Cell::new(0);
+    let vec = Vec::from_elem(Tracked(&drops), 4);
+    drops.set(0);
+
+    let rc: Rc<[Tracked]> = Rc::from(vec);
+    assert_eq!(drops.get(), 0);
+    drop(rc);
+
+    assert_eq!(drops.get(), 4);
+}
+
+#[test]
+fn should_move_zero_sized_box_into_rc() {
+    struct Unit;
+
+    impl Display for Unit {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("unit")
+        }
+    }
+
+    let rc: Rc<[Unit]> = Rc::from(std::boxed::Box::new([Unit, Unit]) as std::boxed::Box<[Unit]>);
+    let unsized_rc = rust_samples::rc_unsize!(Rc::new(Unit) => dyn Display);
+
+    assert_eq!(rc.len(), 2);
+    assert_eq!(rc[1].to_string(), "unit");
+    assert_eq!((*unsized_rc).to_string(), "unit");
+}
+
+#[test]
+fn should_release_strong_count_through_raw() {
+    let rc = Rc::new(String::from("Hello"));
+    let weak = Rc::downgrade(&rc);
+    let ptr = Rc::into_raw(rc);
+
+    unsafe {
+        Rc::increment_strong_count(ptr);
+        assert_eq!(weak.strong_count(), 2);
+        Rc::decrement_strong_count(ptr);
+        Rc::decrement_strong_count(ptr);
+    }
+
+    assert_eq!(weak.upgrade(), None);
+}
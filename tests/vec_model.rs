@@ -0,0 +1,159 @@
+// Model tests, random operation sequences must leave vec::Vec in the same state as std::vec::Vec
+use proptest::prelude::*;
+use rust_samples::vec::vec::Vec;
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[derive(Clone, Debug)]
+enum Op {
+    Push(u8),
+    Pop,
+    Insert(usize, u8),
+    Remove(usize),
+    Truncate(usize),
+    Index(usize),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        4 => any::<u8>().prop_map(Op::Push),
+        2 => Just(Op::Pop),
+        2 => (any::<usize>(), any::<u8>()).prop_map(|(index, value)| Op::Insert(index, value)),
+        2 => any::<usize>().prop_map(Op::Remove),
+        1 => (0..40usize).prop_map(Op::Truncate),
+        2 => any::<usize>().prop_map(Op::Index),
+    ]
+}
+
+// Counts its own drops, so lost or doubled drops show up as a different count
+#[derive(Debug)]
+struct Item {
+    value: u8,
+    drops: Rc<Cell<usize>>,
+}
+
+impl PartialEq for Item {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Drop for Item {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+struct Model {
+    expected: std::vec::Vec<Item>,
+    expected_drops: Rc<Cell<usize>>,
+    actual: Vec<Item>,
+    actual_drops: Rc<Cell<usize>>,
+}
+
+impl Model {
+    fn new(capacity: usize) -> Self {
+        Model {
+            expected: std::vec::Vec::new(),
+            expected_drops: Rc::new(Cell::new(0)),
+            actual: Vec::with_capacity(capacity),
+            actual_drops: Rc::new(Cell::new(0)),
+        }
+    }
+
+    fn items(&self, value: u8) -> (Item, Item) {
+        (
+            Item {
+                value,
+                drops: Rc::clone(&self.expected_drops),
+            },
+            Item {
+                value,
+                drops: Rc::clone(&self.actual_drops),
+            },
+        )
+    }
+
+    fn apply(&mut self, op: &Op) {
+        let len = self.expected.len();
+        match *op {
+            Op::Push(value) => {
+                let (expected, actual) = self.items(value);
+                self.expected.push(expected);
+                self.actual.push(actual);
+            }
+            Op::Pop => {
+                assert_eq!(self.actual.pop(), self.expected.pop());
+            }
+            Op::Insert(index, value) => {
+                let index = index % (len + 1);
+                let (expected, actual) = self.items(value);
+                self.expected.insert(index, expected);
+                self.actual.insert(index, actual);
+            }
+            Op::Remove(index) if len > 0 => {
+                let index = index % len;
+                assert_eq!(self.actual.remove(index), self.expected.remove(index));
+            }
+            Op::Truncate(new_len) => {
+                self.expected.truncate(new_len);
+                self.actual.truncate(new_len);
+            }
+            Op::Index(index) if len > 0 => {
+                let index = index % len;
+                assert_eq!(self.actual[index], self.expected[index]);
+            }
+            Op::Remove(_) | Op::Index(_) => {}
+        }
+    }
+
+    fn check(&self) {
+        assert_eq!(self.actual.len(), self.expected.len());
+        assert_eq!(self.actual.is_empty(), self.expected.is_empty());
+        assert_eq!(&self.actual[0..self.actual.len()], &self.expected[..]);
+        assert_eq!(self.actual_drops.get(), self.expected_drops.get());
+        assert!(self.actual.len() <= self.actual.allocated());
+    }
+}
+
+fn config() -> ProptestConfig {
+    if cfg!(miri) {
+        // Interpreter is slow and isolated from the file system
+        ProptestConfig {
+            cases: 8,
+            failure_persistence: None,
+            ..ProptestConfig::default()
+        }
+    } else {
+        ProptestConfig::default()
+    }
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn should_behave_like_std_vec(capacity in 0..8usize, ops in prop::collection::vec(op(), 0..200)) {
+        let mut model = Model::new(capacity);
+
+        for op in &ops {
+            let allocated = model.actual.allocated();
+            let was_full = model.actual.len() == allocated;
+
+            model.apply(op);
+            model.check();
+
+            // Allocation grows only when full, to INIT_SIZE or four times the previous one
+            let grown = model.actual.allocated();
+            if grown != allocated {
+                prop_assert!(was_full);
+                prop_assert!(grown == 4 || grown == allocated * 4);
+            }
+        }
+
+        let Model { expected, expected_drops, actual, actual_drops } = model;
+        drop(expected);
+        drop(actual);
+        prop_assert_eq!(actual_drops.get(), expected_drops.get());
+    }
+}